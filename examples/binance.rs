@@ -9,19 +9,22 @@ use std::{
 };
 
 use actix_rt::time::sleep;
-use awc::{ws, Client};
+use awc::{Client, ws};
 use chrono::{Offset, TimeZone, Utc};
 use chrono_tz::Asia;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use futures::{prelude::stream::StreamExt, SinkExt};
+use futures::{SinkExt, prelude::stream::StreamExt};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use ratatui::prelude::*;
-use tui_candlestick_chart::{Candle, CandleStickChart, CandleStickChartState, Interval};
+use tui_candlestick_chart::{
+    Candle, CandleStickChart, CandleStickChartState, Interval, Numeric, YAxisPosition,
+    formatter::Currency,
+};
 
 struct App {
     is_loading_previous_candles: Rc<RefCell<bool>>,
@@ -83,27 +86,33 @@ async fn run_app<B: Backend>(
 
         if !*app.is_loading_previous_candles.borrow() {
             let first_timestamp = app.candles.borrow().keys().next().cloned();
-            if app.state.is_needed_previous_candles() {
-                if let Some(first_timestamp) = first_timestamp {
-                    *app.is_loading_previous_candles.borrow_mut() = true;
-                    actix_rt::spawn(binance_btc_usdt_perp_klines(
-                        app.is_loading_previous_candles.clone(),
-                        first_timestamp,
-                        app.candles.clone(),
-                    ));
-                }
+            if app.state.is_needed_previous_candles()
+                && let Some(first_timestamp) = first_timestamp
+            {
+                *app.is_loading_previous_candles.borrow_mut() = true;
+                actix_rt::spawn(binance_btc_usdt_perp_klines(
+                    app.is_loading_previous_candles.clone(),
+                    first_timestamp,
+                    app.candles.clone(),
+                ));
             }
         }
 
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
-        if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => return Ok(()),
-                    KeyCode::Left => app.state.try_move_backward(),
-                    KeyCode::Right => app.state.try_move_forward(),
-                    _ => {}
-                }
+        if crossterm::event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+        {
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Left => app.state.try_move_backward(),
+                KeyCode::Right => app.state.try_move_forward(),
+                KeyCode::Char('+') => app.state.zoom_in(),
+                KeyCode::Char('-') => app.state.zoom_out(),
+                KeyCode::PageUp => app.state.page_backward(),
+                KeyCode::PageDown => app.state.page_forward(),
+                KeyCode::Home => app.state.jump_to_oldest(),
+                KeyCode::End => app.state.jump_to_latest(),
+                _ => {}
             }
         }
         if last_tick.elapsed() >= tick_rate {
@@ -128,7 +137,7 @@ async fn binance_btc_usdt_perp_agg_trade(candles: Rc<RefCell<BTreeMap<i64, Candl
         let response = connection.next().await.unwrap().unwrap();
         let ws::Frame::Text(bytes) = response else {
             if let ws::Frame::Ping(_) = response {
-                let _ = connection
+                connection
                     .send(ws::Message::Pong(([0x0A].as_slice()).into()))
                     .await
                     .unwrap();
@@ -136,10 +145,11 @@ async fn binance_btc_usdt_perp_agg_trade(candles: Rc<RefCell<BTreeMap<i64, Candl
             continue;
         };
         let json: serde_json::Value =
-            serde_json::from_str(&std::str::from_utf8(&bytes.to_vec()).unwrap()).unwrap();
+            serde_json::from_str(std::str::from_utf8(&bytes).unwrap()).unwrap();
 
         let t = json["T"].as_i64().unwrap() / 60_000 * 60_000;
         let p = OrderedFloat::from(json["p"].as_str().unwrap().parse::<f64>().unwrap());
+        let q = OrderedFloat::from(json["q"].as_str().unwrap().parse::<f64>().unwrap());
         candles
             .borrow_mut()
             .entry(t)
@@ -147,8 +157,9 @@ async fn binance_btc_usdt_perp_agg_trade(candles: Rc<RefCell<BTreeMap<i64, Candl
                 c.low = min(c.low, p);
                 c.high = max(c.high, p);
                 c.close = p;
+                c.volume = Some(c.volume.unwrap_or_default() + q);
             })
-            .or_insert(Candle::new(t, *p, *p, *p, *p).unwrap());
+            .or_insert(Candle::new(t, *p, *p, *p, *p).unwrap().with_volume(*q));
     }
 }
 
@@ -169,7 +180,7 @@ async fn binance_btc_usdt_perp_klines(
         .await
         .unwrap();
     let json: serde_json::Value =
-        serde_json::from_str(&std::str::from_utf8(&bytes.to_vec()).unwrap()).unwrap();
+        serde_json::from_str(std::str::from_utf8(&bytes).unwrap()).unwrap();
 
     let mut candles = candles.borrow_mut();
     for kline in json.as_array().unwrap() {
@@ -182,7 +193,8 @@ async fn binance_btc_usdt_perp_klines(
             data[3].as_str().unwrap().parse::<f64>().unwrap(),
             data[4].as_str().unwrap().parse::<f64>().unwrap(),
         )
-        .unwrap();
+        .unwrap()
+        .with_volume(data[5].as_str().unwrap().parse::<f64>().unwrap());
         candles.insert(timestamp, candle);
     }
     *is_loading_previous_candles.borrow_mut() = false;
@@ -202,6 +214,10 @@ fn ui(f: &mut Frame, app: &mut App) {
             Asia::Seoul
                 .offset_from_utc_date(&Utc::now().naive_utc().date())
                 .fix(),
-        );
+        )
+        .y_axis_numeric(Numeric::tick_size(0.1).formatter(Currency::new("$")))
+        .y_axis_position(YAxisPosition::Right)
+        .show_last_price(true)
+        .show_volume(true);
     f.render_stateful_widget(chart, f.area(), &mut app.state);
 }
@@ -5,16 +5,22 @@ use std::{
 };
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::prelude::*;
-use tui_candlestick_chart::{Candle, CandleStickChart, CandleStickChartState, Interval};
+use tui_candlestick_chart::{
+    Candle, CandleStickChart, CandleStickChartState, ChartStyle, Interval, MouseEvent,
+    MouseEventKind, Overlay, Pane,
+    indicators::{Indicator, Rsi, Sma},
+};
 
 struct App {
     candles: Vec<Candle>,
     state: CandleStickChartState,
+    chart_style: ChartStyle,
+    heikin_ashi: bool,
 }
 
 impl App {
@@ -523,8 +529,20 @@ impl App {
                 Candle::new(1704006840000, 42313.99, 42314.00, 42313.99, 42313.99).unwrap(),
             ],
             state: CandleStickChartState::default(),
+            chart_style: ChartStyle::default(),
+            heikin_ashi: false,
         }
     }
+
+    fn next_chart_style(&mut self) {
+        self.chart_style = match self.chart_style {
+            ChartStyle::Candles => ChartStyle::OhlcBars,
+            ChartStyle::OhlcBars => ChartStyle::HollowCandles,
+            ChartStyle::HollowCandles => ChartStyle::Line,
+            ChartStyle::Line => ChartStyle::Area,
+            ChartStyle::Area | ChartStyle::PointAndFigure | ChartStyle::Kagi => ChartStyle::Candles,
+        };
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -567,13 +585,35 @@ fn run_app<B: Backend>(
 
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
+            match event::read()? {
+                Event::Key(key) => match key.code {
                     KeyCode::Char('q') => return Ok(()),
                     KeyCode::Left => app.state.try_move_backward(),
                     KeyCode::Right => app.state.try_move_forward(),
+                    KeyCode::Char('+') => app.state.zoom_in(),
+                    KeyCode::Char('-') => app.state.zoom_out(),
+                    KeyCode::PageUp => app.state.page_backward(),
+                    KeyCode::PageDown => app.state.page_forward(),
+                    KeyCode::Home => app.state.jump_to_oldest(),
+                    KeyCode::End => app.state.jump_to_latest(),
+                    KeyCode::Char('s') => app.next_chart_style(),
+                    KeyCode::Char('h') => app.heikin_ashi = !app.heikin_ashi,
                     _ => {}
+                },
+                Event::Mouse(mouse) => {
+                    let kind = match mouse.kind {
+                        event::MouseEventKind::Down(MouseButton::Left) => MouseEventKind::Down,
+                        event::MouseEventKind::Drag(MouseButton::Left) => MouseEventKind::Drag,
+                        event::MouseEventKind::Up(MouseButton::Left) => MouseEventKind::Up,
+                        event::MouseEventKind::ScrollUp => MouseEventKind::ScrollUp,
+                        event::MouseEventKind::ScrollDown => MouseEventKind::ScrollDown,
+                        _ => continue,
+                    };
+                    let area = terminal.get_frame().area();
+                    app.state
+                        .handle_mouse_event(MouseEvent::new(mouse.column, mouse.row, kind), area);
                 }
+                _ => {}
             }
         }
         if last_tick.elapsed() >= tick_rate {
@@ -583,6 +623,22 @@ fn run_app<B: Backend>(
 }
 
 fn ui(f: &mut Frame, app: &mut App) {
-    let chart = CandleStickChart::new(Interval::OneMinute).candles(app.candles.clone());
+    let chart = CandleStickChart::new(Interval::OneMinute)
+        .candles(app.candles.clone())
+        .chart_style(app.chart_style)
+        .heikin_ashi(app.heikin_ashi)
+        .overlay(Overlay::new(
+            Sma::new(20).compute(&app.candles),
+            Color::Yellow,
+        ))
+        .pane(
+            Pane::new()
+                .line(Overlay::new(
+                    Rsi::new(14).compute(&app.candles),
+                    Color::Magenta,
+                ))
+                .range(0., 100.)
+                .guide_lines(vec![30., 70.]),
+        );
     f.render_stateful_widget(chart, f.area(), &mut app.state);
 }
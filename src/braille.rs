@@ -0,0 +1,177 @@
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Style},
+    symbols::braille::{BLANK, DOTS},
+};
+
+use crate::symbols::UNICODE_VOID;
+
+/// Drawing surface with 2x4 braille dots per cell.
+///
+/// Dot coordinates start at the bottom-left corner and grow rightwards and upwards, so they can
+/// be derived from `YAxis::calc_y` without flipping.
+pub(crate) struct BrailleCanvas {
+    width: u16,
+    height: u16,
+    cells: Vec<(u16, Option<Color>)>,
+}
+
+impl BrailleCanvas {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![(0, None); width as usize * height as usize],
+        }
+    }
+
+    pub fn dot_width(&self) -> i64 {
+        self.width as i64 * 2
+    }
+
+    pub fn dot_height(&self) -> i64 {
+        self.height as i64 * 4
+    }
+
+    /// Set a single dot, ignoring dots outside of the canvas
+    pub fn set(&mut self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x >= self.width as i64 * 2 || y >= self.dot_height() {
+            return;
+        }
+
+        let column = (x / 2) as usize;
+        let row = (self.height as i64 - 1 - y / 4) as usize;
+        let cell = &mut self.cells[row * self.width as usize + column];
+        cell.0 |= DOTS[3 - (y % 4) as usize][(x % 2) as usize];
+        cell.1 = Some(color);
+    }
+
+    /// Draw a straight line between two dots (Bresenham)
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64), color: Color) {
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            self.set(x, y, color);
+            if (x, y) == to {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draw the part of a line between two fractional dots that lies inside the canvas, so far
+    /// away end points do not have to be walked dot by dot (Liang-Barsky)
+    pub fn clipped_line(&mut self, from: (f64, f64), to: (f64, f64), color: Color) {
+        if ![from.0, from.1, to.0, to.1].iter().all(|v| v.is_finite()) {
+            return;
+        }
+        let (x_max, y_max) = (self.width as f64 * 2. - 1., self.dot_height() as f64 - 1.);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let (mut t0, mut t1) = (0f64, 1f64);
+        for (p, q) in [
+            (-dx, from.0),
+            (dx, x_max - from.0),
+            (-dy, from.1),
+            (dy, y_max - from.1),
+        ] {
+            if p == 0. {
+                if q < 0. {
+                    return;
+                }
+            } else if p < 0. {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return;
+        }
+
+        let dot = |t: f64| {
+            (
+                (from.0 + dx * t).round() as i64,
+                (from.1 + dy * t).round() as i64,
+            )
+        };
+        self.line(dot(t0), dot(t1), color);
+    }
+
+    /// Write every cell holding at least one dot; empty cells keep what is underneath
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.render_cells(area, buf, false);
+    }
+
+    /// Like [`render`](Self::render), but only on cells of `buf` that are still empty, so the
+    /// dots stay behind candles and other content
+    pub fn render_behind(&self, area: Rect, buf: &mut Buffer) {
+        self.render_cells(area, buf, true);
+    }
+
+    fn render_cells(&self, area: Rect, buf: &mut Buffer, only_void: bool) {
+        for (i, (dots, color)) in self.cells.iter().enumerate() {
+            let Some(color) = color else {
+                continue;
+            };
+            let cell_x = area.x + (i % self.width as usize) as u16;
+            let cell_y = area.y + (i / self.width as usize) as u16;
+            let symbol = char::from_u32((BLANK | dots) as u32).unwrap_or(' ');
+            if let Some(cell) = buf.cell_mut((cell_x, cell_y))
+                && (!only_void || cell.symbol() == UNICODE_VOID)
+            {
+                cell.set_char(symbol).set_style(Style::default().fg(*color));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{prelude::*, style::Color};
+
+    use super::BrailleCanvas;
+
+    #[test]
+    fn line() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.line((0, 0), (3, 3), Color::Reset);
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::empty(area);
+        canvas.render(area, &mut buffer);
+        assert_eq!(buffer, Buffer::with_lines(vec!["⡠⠊"]));
+    }
+
+    #[test]
+    fn clipped_line() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.clipped_line((-1e9, -1e9 + 1.), (1e9, 1e9 + 1.), Color::Reset);
+        canvas.clipped_line((0., 10.), (3., 20.), Color::Reset);
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::empty(area);
+        canvas.render(area, &mut buffer);
+        assert_eq!(buffer, Buffer::with_lines(vec!["⠔⠁"]));
+    }
+
+    #[test]
+    fn render_behind() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.line((0, 0), (3, 0), Color::Reset);
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::with_lines(vec!["┃ "]);
+        canvas.render_behind(area, &mut buffer);
+        assert_eq!(buffer, Buffer::with_lines(vec!["┃⣀"]));
+    }
+}
@@ -3,8 +3,9 @@ use std::cmp::{max, min};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 
-use crate::{symbols::*, y_axis::YAxis, Float};
+use crate::{Float, symbols::*, y_axis::YAxis};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CandleType {
     Bearish,
     Bullish,
@@ -17,6 +18,7 @@ pub struct Candle {
     pub high: Float,
     pub low: Float,
     pub close: Float,
+    pub volume: Option<Float>,
 }
 
 impl Candle {
@@ -28,28 +30,193 @@ impl Candle {
                 high: OrderedFloat::from(high),
                 low: OrderedFloat::from(low),
                 close: OrderedFloat::from(close),
+                volume: None,
             })
         } else {
             None
         }
     }
 
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = Some(OrderedFloat::from(volume));
+        self
+    }
+
+    /// Merge consecutive candles into one: first open, last close, min low, max high
+    /// and summed volume. The merged candle keeps the first timestamp.
+    pub(crate) fn merge(candles: &[Candle]) -> Option<Self> {
+        let first = candles.first()?;
+        let last = candles.last()?;
+        let volume = candles
+            .iter()
+            .filter_map(|c| c.volume)
+            .fold(None, |acc: Option<Float>, v| {
+                Some(acc.unwrap_or_default() + v)
+            });
+
+        Some(Self {
+            timestamp: first.timestamp,
+            open: first.open,
+            high: candles.iter().map(|c| c.high).max()?,
+            low: candles.iter().map(|c| c.low).min()?,
+            close: last.close,
+            volume,
+        })
+    }
+
+    pub(crate) fn candle_type(&self) -> CandleType {
+        if self.open <= self.close {
+            CandleType::Bullish
+        } else {
+            CandleType::Bearish
+        }
+    }
+
     pub(crate) fn render(&self, y_axis: &YAxis) -> (CandleType, Vec<&str>) {
         self.render_internal(y_axis, 1)
     }
 
-    pub(crate) fn render_stretched(&self, y_axis: &YAxis, width: u16) -> (CandleType, Vec<Vec<&str>>) {
+    pub(crate) fn render_stretched(
+        &self,
+        y_axis: &YAxis,
+        width: u16,
+    ) -> (CandleType, Vec<Vec<&str>>) {
         let (candle_type, base_chars) = self.render_internal(y_axis, 1);
         let mut stretched_result = Vec::new();
-        
+
         for char in base_chars {
             let row = self.stretch_char(char, width);
             stretched_result.push(row);
         }
-        
+
         (candle_type, stretched_result)
     }
 
+    /// OHLC bar: a high-low line in the middle column with the open ticked to the left and the
+    /// close ticked to the right. Wider bars lengthen the ticks.
+    pub(crate) fn render_bar(&self, y_axis: &YAxis, width: u16) -> Vec<Vec<&'static str>> {
+        let (high, low) = (y_axis.row(self.high), y_axis.row(self.low));
+        let (open, close) = (y_axis.row(self.open), y_axis.row(self.close));
+        let center = (width.max(1) - 1) as usize / 2;
+
+        (0..y_axis.height())
+            .map(|y| {
+                let mut row = vec![UNICODE_VOID; width.max(1) as usize];
+                if !(high..=low).contains(&y) {
+                    return row;
+                }
+                row[center] = match (y == open, y == close) {
+                    (true, true) => UNICODE_BAR_OPEN_CLOSE,
+                    (true, false) => UNICODE_BAR_OPEN,
+                    (false, true) => UNICODE_BAR_CLOSE,
+                    (false, false) => UNICODE_WICK,
+                };
+                // ticks end half a cell before the column edge, like stretched bodies
+                if y == open && center > 0 {
+                    row[..center].fill(UNICODE_BAR_TICK);
+                    row[0] = UNICODE_BAR_TICK_LEFT_END;
+                }
+                let last = row.len() - 1;
+                if y == close && center < last {
+                    row[center + 1..].fill(UNICODE_BAR_TICK);
+                    row[last] = UNICODE_BAR_TICK_RIGHT_END;
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// Hollow candle: the body of a rising candle (close above open) is outlined, the body of a
+    /// falling one stays filled. A single column outlines with double lines, wider columns with
+    /// a box around the body.
+    pub(crate) fn render_hollow(&self, y_axis: &YAxis, width: u16) -> Vec<Vec<&str>> {
+        let (candle_type, base_chars) = self.render_internal(y_axis, 1);
+        let hollow = matches!(candle_type, CandleType::Bullish);
+
+        if width <= 1 {
+            return base_chars
+                .into_iter()
+                .map(|char| match char {
+                    UNICODE_BODY if hollow => vec![UNICODE_HOLLOW_BODY],
+                    UNICODE_UPPER_BODY | UNICODE_HALF_BODY_BOTTOM if hollow => {
+                        vec![UNICODE_HOLLOW_BODY_TOP]
+                    }
+                    UNICODE_LOWER_BODY | UNICODE_HALF_BODY_TOP if hollow => {
+                        vec![UNICODE_HOLLOW_BODY_BOTTOM]
+                    }
+                    _ => vec![char],
+                })
+                .collect();
+        }
+
+        let mut rows = base_chars
+            .iter()
+            .map(|char| self.stretch_char(char, width))
+            .collect_vec();
+        let Some((top, bottom)) = body_rows(&base_chars) else {
+            return rows;
+        };
+        if !hollow {
+            return rows;
+        }
+
+        let last = width as usize - 1;
+        for (y, row) in rows.iter_mut().enumerate().take(bottom + 1).skip(top) {
+            let (left, edge, right) = match (y == top, y == bottom) {
+                (true, true) => (
+                    UNICODE_HOLLOW_LEFT,
+                    UNICODE_HOLLOW_EDGE,
+                    UNICODE_HOLLOW_RIGHT,
+                ),
+                (true, false) => (
+                    UNICODE_HOLLOW_TOP_LEFT,
+                    UNICODE_HOLLOW_EDGE,
+                    UNICODE_HOLLOW_TOP_RIGHT,
+                ),
+                (false, true) => (
+                    UNICODE_HOLLOW_BOTTOM_LEFT,
+                    UNICODE_HOLLOW_EDGE,
+                    UNICODE_HOLLOW_BOTTOM_RIGHT,
+                ),
+                (false, false) => (UNICODE_WICK, UNICODE_VOID, UNICODE_WICK),
+            };
+            row.fill(edge);
+            row[0] = left;
+            row[last] = right;
+        }
+
+        // join the wicks to the middle of the box, when the box has a middle cell
+        if !width.is_multiple_of(2) {
+            let center = last / 2;
+            let wick_above = top > 0 && base_chars[top - 1] != UNICODE_VOID;
+            let wick_below = base_chars
+                .get(bottom + 1)
+                .is_some_and(|c| *c != UNICODE_VOID);
+            if top == bottom {
+                rows[top][center] = match (wick_above, wick_below) {
+                    (true, true) => UNICODE_HOLLOW_WICK_THROUGH,
+                    (true, false) => UNICODE_HOLLOW_WICK_ABOVE,
+                    (false, true) => UNICODE_HOLLOW_WICK_BELOW,
+                    (false, false) => UNICODE_HOLLOW_EDGE,
+                };
+            } else {
+                if wick_above {
+                    rows[top][center] = UNICODE_HOLLOW_WICK_ABOVE;
+                }
+                if wick_below {
+                    rows[bottom][center] = UNICODE_HOLLOW_WICK_BELOW;
+                }
+            }
+        }
+        rows
+    }
+
+    /// First and last row from the top holding the body, `None` when it is not visible
+    pub(crate) fn body_rows(&self, y_axis: &YAxis) -> Option<(usize, usize)> {
+        let (_, chars) = self.render_internal(y_axis, 1);
+        body_rows(&chars)
+    }
+
     fn render_internal(&self, y_axis: &YAxis, _width: u16) -> (CandleType, Vec<&str>) {
         let open = y_axis.calc_y(self.open);
         let close = y_axis.calc_y(self.close);
@@ -73,13 +240,8 @@ impl Candle {
                         is_body = true;
                         UNICODE_BODY
                     } else if high_max_diff < 0.75 {
-                        if is_body {
-                            is_body = true;
-                            UNICODE_BODY
-                        } else {
-                            is_body = true;
-                            UNICODE_BODY
-                        }
+                        is_body = true;
+                        UNICODE_UPPER_BODY
                     } else {
                         UNICODE_WICK
                     }
@@ -100,13 +262,10 @@ impl Candle {
                     if min_low_diff < 0.25 {
                         is_body = true;
                         UNICODE_BODY
-                    } else if min_low_diff < 0.75 {
-                        if is_body {
-                            is_body = false;
-                            UNICODE_WICK
-                        } else {
-                            UNICODE_WICK
-                        }
+                    } else if min_low_diff < 0.75 && is_body {
+                        // the body ends inside this row
+                        is_body = false;
+                        UNICODE_LOWER_BODY
                     } else {
                         UNICODE_WICK
                     }
@@ -131,18 +290,12 @@ impl Candle {
             tracing::error!("The result of candle rendering is broken. Please report it.")
         }
 
-        let candle_type = if open <= close {
-            CandleType::Bullish
-        } else {
-            CandleType::Bearish
-        };
-
-        (candle_type, result)
+        (self.candle_type(), result)
     }
 
     fn stretch_char(&self, char: &str, width: u16) -> Vec<&'static str> {
         match char {
-            UNICODE_BODY | UNICODE_HALF_BODY_BOTTOM | UNICODE_HALF_BODY_TOP => {
+            char if is_body(char) => {
                 if width == 1 {
                     vec![UNICODE_BODY]
                 } else {
@@ -156,59 +309,57 @@ impl Candle {
                     self.stretch_wick(width)
                 }
             }
-            _ => vec![UNICODE_VOID; width as usize]
+            _ => vec![UNICODE_VOID; width as usize],
         }
     }
 
     fn stretch_body(&self, width: u16) -> Vec<&'static str> {
-        let mut result = Vec::new();
-        
-        if width == 2 {
-            result.push(UNICODE_RIGHT_HALF_BLOCK);
-            result.push(UNICODE_LEFT_HALF_BLOCK);
-        } else if width > 2 {
-            result.push(UNICODE_RIGHT_HALF_BLOCK);
-            for _ in 0..(width - 2) {
-                result.push(UNICODE_FULL_BLOCK);
-            }
+        if width >= 2 {
+            let mut result = vec![UNICODE_RIGHT_HALF_BLOCK];
+            result.extend(vec![UNICODE_FULL_BLOCK; width as usize - 2]);
             result.push(UNICODE_LEFT_HALF_BLOCK);
+            result
         } else {
-            result.push(UNICODE_BODY);
+            vec![UNICODE_BODY]
         }
-        
-        result
     }
 
     fn stretch_wick(&self, width: u16) -> Vec<&'static str> {
-        let mut result = Vec::new();
-        
-        if width % 2 == 0 {
+        let (center_offset, center) = if width.is_multiple_of(2) {
             // Even width: use eighth blocks
-            let center_offset = (width - 2) / 2;
-            for _ in 0..center_offset {
-                result.push(UNICODE_VOID);
-            }
-            result.push(UNICODE_RIGHT_EIGHTH_BLOCK);
-            result.push(UNICODE_LEFT_EIGHTH_BLOCK);
-            for _ in 0..center_offset {
-                result.push(UNICODE_VOID);
-            }
+            (
+                (width - 2) / 2,
+                vec![UNICODE_RIGHT_EIGHTH_BLOCK, UNICODE_LEFT_EIGHTH_BLOCK],
+            )
         } else {
             // Odd width: use single wick character
-            let center_offset = (width - 1) / 2;
-            for _ in 0..center_offset {
-                result.push(UNICODE_VOID);
-            }
-            result.push(UNICODE_WICK);
-            for _ in 0..center_offset {
-                result.push(UNICODE_VOID);
-            }
-        }
-        
+            ((width - 1) / 2, vec![UNICODE_WICK])
+        };
+
+        let mut result = vec![UNICODE_VOID; center_offset as usize];
+        result.extend(center);
+        result.extend(vec![UNICODE_VOID; center_offset as usize]);
         result
     }
 }
 
+fn body_rows(chars: &[&str]) -> Option<(usize, usize)> {
+    let mut rows = chars.iter().positions(|char| is_body(char));
+    let top = rows.next()?;
+    Some((top, rows.next_back().unwrap_or(top)))
+}
+
+fn is_body(char: &str) -> bool {
+    matches!(
+        char,
+        UNICODE_BODY
+            | UNICODE_UPPER_BODY
+            | UNICODE_LOWER_BODY
+            | UNICODE_HALF_BODY_BOTTOM
+            | UNICODE_HALF_BODY_TOP
+    )
+}
+
 fn test_continuous_graph(mut chars: Vec<&str>) -> bool {
     if chars.iter().all(|&c| c == UNICODE_VOID) {
         return false;
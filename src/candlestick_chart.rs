@@ -1,18 +1,29 @@
+use std::cmp::{max, max_by_key, min};
+
 use chrono::{FixedOffset, Offset, Utc};
 use itertools::Itertools;
+use ordered_float::OrderedFloat;
 use ratatui::{
     prelude::{Buffer, Rect},
-    style::{Color, Style, Styled},
+    style::{Color, Style, Styled, Stylize},
     widgets::StatefulWidget,
 };
 
 use crate::{
+    CandleStickChartState, Float,
+    braille::BrailleCanvas,
     candle::{Candle, CandleType},
-    candlestick_chart_state::CandleStikcChartInfo,
+    candlestick_chart_state::{CandleStikcChartInfo, Scale},
+    drawing::Drawing,
+    indicators,
+    marker::{self, Marker},
+    overlay::Overlay,
+    pane::Pane,
+    price_level::{PriceBand, PriceLine},
     symbols::*,
+    tooltip::{Tooltip, TooltipPosition},
     x_axis::{Interval, XAxis},
-    y_axis::{Numeric, YAxis},
-    CandleStickChartState,
+    y_axis::{Numeric, PriceScale, YAxis, YAxisPosition},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,14 +34,75 @@ pub enum ChartFitMode {
     Fit,
 }
 
+/// How each column of prices is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChartStyle {
+    #[default]
+    Candles,
+    /// High-low line with the open ticked to the left and the close ticked to the right
+    OhlcBars,
+    /// Candles outlined when closing above their open and filled otherwise, colored by the
+    /// close against the previous close
+    HollowCandles,
+    /// Braille line through the closes
+    Line,
+    /// Closes filled down to the bottom of the price area with block glyphs
+    Area,
+    /// X in every row covered by a rising column and O for a falling one, e.g. for
+    /// [`PointAndFigure`](crate::PointAndFigure) columns
+    PointAndFigure,
+    /// Vertical lines joined by horizontal shoulders and waists, heavy in the bullish color for
+    /// yang lines and light in the bearish color for yin lines, e.g. for [`Kagi`](crate::Kagi)
+    /// lines
+    Kagi,
+}
+
+/// Horizontal placement of a rendered (possibly merged) candle, relative to the plot area
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Column {
+    pub(crate) candle: Candle,
+    /// Timestamp of the last candle merged into this column
+    pub(crate) end_timestamp: i64,
+    pub(crate) x: u16,
+    pub(crate) width: u16,
+}
+
+impl Column {
+    /// Index of the column covering `timestamp`, if any
+    pub(crate) fn find(columns: &[Column], timestamp: i64) -> Option<usize> {
+        let index = columns
+            .partition_point(|c| c.candle.timestamp <= timestamp)
+            .checked_sub(1)?;
+        (timestamp <= columns[index].end_timestamp).then_some(index)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CandleStickChart {
     /// Candle interval
     interval: Interval,
     /// Candle data
     candles: Vec<Candle>,
+    /// Lines drawn over the candles
+    overlays: Vec<Overlay>,
+    /// Panes stacked below the price area
+    panes: Vec<Pane>,
+    /// Horizontal lines at fixed prices
+    price_lines: Vec<PriceLine>,
+    /// Shaded zones between two prices
+    price_bands: Vec<PriceBand>,
+    /// Glyphs marking events on candles
+    markers: Vec<Marker>,
+    /// Trend lines, rays and Fibonacci levels
+    drawings: Vec<Drawing>,
+    /// Price area height relative to the panes
+    price_height_ratio: u16,
     /// y axis scale/precision
     numeric: Numeric,
+    /// Price axis mapping
+    price_scale: PriceScale,
+    /// Preferred rows between two y axis labels
+    y_axis_tick_spacing: u16,
     /// Widget style
     style: Style,
     /// Candle style,
@@ -43,10 +115,32 @@ pub struct CandleStickChart {
     display_timezone: FixedOffset,
     /// show/hide y axis
     show_y_axis: bool,
+    /// Sides of the plot area the y axis is drawn on
+    y_axis_position: YAxisPosition,
     /// show/hide x axis
     show_x_axis: bool,
+    /// show/hide the cursor line and its x axis label
+    show_cursor: bool,
+    cursor_color: Color,
+    /// show/hide the OHLC info box of the cursor candle
+    show_tooltip: bool,
+    tooltip_position: TooltipPosition,
+    /// show/hide the dashed line and y axis tag at the latest close
+    show_last_price: bool,
+    /// show/hide volume pane
+    show_volume: bool,
+    /// Volume pane height in rows
+    volume_height: u16,
     /// Chart fitting mode
     fit_mode: ChartFitMode,
+    /// Candles or bars
+    chart_style: ChartStyle,
+    /// Draw Heikin-Ashi candles computed from the candles
+    heikin_ashi: bool,
+    /// Candle timestamps are consecutive indices
+    indexed: bool,
+    /// Start timestamp of each indexed candle
+    index_timestamps: Vec<i64>,
     /// Maximum width for a single candle when stretching
     max_width: u16,
 }
@@ -56,17 +150,38 @@ impl CandleStickChart {
         Self {
             interval,
             candles: Vec::default(),
+            overlays: Vec::default(),
+            panes: Vec::default(),
+            price_lines: Vec::default(),
+            price_bands: Vec::default(),
+            markers: Vec::default(),
+            drawings: Vec::default(),
+            price_height_ratio: 3,
             numeric: Numeric::default(),
+            price_scale: PriceScale::default(),
+            y_axis_tick_spacing: 4,
             style: Style::default(),
             bearish_color: Color::Rgb(234, 74, 90),
             bullish_color: Color::Rgb(52, 208, 88),
-            bearish_wick_color: Color::Rgb(234, 74, 90),  // Same as body by default
-            bullish_wick_color: Color::Rgb(52, 208, 88),  // Same as body by default
+            bearish_wick_color: Color::Rgb(234, 74, 90), // Same as body by default
+            bullish_wick_color: Color::Rgb(52, 208, 88), // Same as body by default
             display_timezone: Utc.fix(),
             show_y_axis: true,
+            y_axis_position: YAxisPosition::default(),
             show_x_axis: true,
-            fit_mode: ChartFitMode::Fixed,  // Default to fixed mode
-            max_width: 10, // Default max width for stretching
+            show_cursor: true,
+            cursor_color: Color::DarkGray,
+            show_tooltip: true,
+            tooltip_position: TooltipPosition::Floating,
+            show_last_price: false,
+            show_volume: false,
+            volume_height: 4,
+            fit_mode: ChartFitMode::Fixed, // Default to fixed mode
+            max_width: 10,                 // Default max width for stretching
+            chart_style: ChartStyle::default(),
+            heikin_ashi: false,
+            indexed: false,
+            index_timestamps: Vec::default(),
         }
     }
 
@@ -75,11 +190,88 @@ impl CandleStickChart {
         self
     }
 
+    pub fn overlays(mut self, overlays: Vec<Overlay>) -> Self {
+        self.overlays = overlays;
+        self
+    }
+
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    pub fn panes(mut self, panes: Vec<Pane>) -> Self {
+        self.panes = panes;
+        self
+    }
+
+    pub fn pane(mut self, pane: Pane) -> Self {
+        self.panes.push(pane);
+        self
+    }
+
+    pub fn price_lines(mut self, lines: Vec<PriceLine>) -> Self {
+        self.price_lines = lines;
+        self
+    }
+
+    pub fn price_line(mut self, line: PriceLine) -> Self {
+        self.price_lines.push(line);
+        self
+    }
+
+    pub fn price_bands(mut self, bands: Vec<PriceBand>) -> Self {
+        self.price_bands = bands;
+        self
+    }
+
+    pub fn price_band(mut self, band: PriceBand) -> Self {
+        self.price_bands.push(band);
+        self
+    }
+
+    pub fn markers(mut self, markers: Vec<Marker>) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.markers.push(marker);
+        self
+    }
+
+    pub fn drawings(mut self, drawings: Vec<Drawing>) -> Self {
+        self.drawings = drawings;
+        self
+    }
+
+    pub fn drawing(mut self, drawing: Drawing) -> Self {
+        self.drawings.push(drawing);
+        self
+    }
+
+    /// Height of the price area relative to the `height_ratio` of each pane
+    pub fn price_height_ratio(mut self, ratio: u16) -> Self {
+        self.price_height_ratio = max(1, ratio);
+        self
+    }
+
     pub fn y_axis_numeric(mut self, numeric: Numeric) -> Self {
         self.numeric = numeric;
         self
     }
 
+    pub fn price_scale(mut self, scale: PriceScale) -> Self {
+        self.price_scale = scale;
+        self
+    }
+
+    /// Preferred number of rows between two y axis labels, lower values label more prices
+    pub fn y_axis_tick_spacing(mut self, rows: u16) -> Self {
+        self.y_axis_tick_spacing = max(1, rows);
+        self
+    }
+
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
@@ -115,321 +307,1132 @@ impl CandleStickChart {
         self
     }
 
+    pub fn y_axis_position(mut self, position: YAxisPosition) -> Self {
+        self.y_axis_position = position;
+        self
+    }
+
     pub fn show_x_axis(mut self, show: bool) -> Self {
         self.show_x_axis = show;
         self
     }
 
+    /// Show a vertical line on the cursor candle and highlight its time on the x axis
+    pub fn show_cursor(mut self, show: bool) -> Self {
+        self.show_cursor = show;
+        self
+    }
+
+    pub fn cursor_color(mut self, color: Color) -> Self {
+        self.cursor_color = color;
+        self
+    }
+
+    /// Show the OHLC values of the cursor candle in an info box
+    pub fn show_tooltip(mut self, show: bool) -> Self {
+        self.show_tooltip = show;
+        self
+    }
+
+    /// Dashed line at the close of the latest candle with its exact price tagged on the y axis,
+    /// colored by the direction of that candle. Only drawn while the view follows the latest
+    /// candle.
+    pub fn show_last_price(mut self, show: bool) -> Self {
+        self.show_last_price = show;
+        self
+    }
+
+    pub fn tooltip_position(mut self, position: TooltipPosition) -> Self {
+        self.tooltip_position = position;
+        self
+    }
+
+    /// Show a volume pane beneath the candles. Candles without volume leave their column empty.
+    pub fn show_volume(mut self, show: bool) -> Self {
+        self.show_volume = show;
+        self
+    }
+
+    pub fn volume_height(mut self, height: u16) -> Self {
+        self.volume_height = height;
+        self
+    }
+
     pub fn fit_mode(mut self, mode: ChartFitMode) -> Self {
         self.fit_mode = mode;
         self
     }
 
     pub fn max_width(mut self, max_width: u16) -> Self {
-        self.max_width = std::cmp::max(1, max_width); // Ensure minimum width of 1
+        self.max_width = max(1, max_width); // Ensure minimum width of 1
         self
     }
-}
 
-impl Styled for CandleStickChart {
-    type Item = CandleStickChart;
+    pub fn chart_style(mut self, style: ChartStyle) -> Self {
+        self.chart_style = style;
+        self
+    }
 
-    fn style(&self) -> Style {
-        self.style
+    /// Draw Heikin-Ashi candles instead of the candles. The tooltip and the state keep reporting
+    /// the real prices.
+    pub fn heikin_ashi(mut self, heikin_ashi: bool) -> Self {
+        self.heikin_ashi = heikin_ashi;
+        self
     }
 
-    fn set_style<S: Into<Style>>(self, style: S) -> Self::Item {
-        self.style(style.into())
+    /// Treat the candle timestamps as consecutive indices `0, 1, 2, ..` instead of times, e.g.
+    /// for [`Renko`](crate::Renko) bricks. The interval is ignored and the x axis is labeled with
+    /// the indices.
+    pub fn indexed(mut self, indexed: bool) -> Self {
+        self.indexed = indexed;
+        self
     }
-}
 
-impl StatefulWidget for CandleStickChart {
-    type State = CandleStickChartState;
+    /// Timestamp where each indexed candle starts, e.g. from
+    /// [`PointAndFigure::timestamps`](crate::PointAndFigure::timestamps). The x axis then labels
+    /// the candles starting a new month with the month and year instead of their indices.
+    pub fn index_timestamps(mut self, timestamps: Vec<i64>) -> Self {
+        self.index_timestamps = timestamps;
+        self
+    }
 
-    /// render like:
-    /// |---|-----------------------|
-    /// | y |                       |
-    /// |   |                       |
-    /// | a |                       |
-    /// | x |                       |
-    /// | i |                       |
-    /// | s |       chart data      |
-    /// |   |                       |
-    /// | a |                       |
-    /// | r |                       |
-    /// | e |                       |
-    /// | a |                       |
-    /// |---|-----------------------|
-    ///     |      x axis area      |
-    ///     |-----------------------|
-    ///
-    ///
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        if self.candles.is_empty() {
-            return;
+    /// Split the height between the price area and the panes by their height ratios.
+    /// The price area keeps the rounding remainder.
+    fn split_heights(&self, height: u16) -> (u16, Vec<u16>) {
+        let total_ratio = self.price_height_ratio as u32
+            + self
+                .panes
+                .iter()
+                .map(|p| p.height_ratio as u32)
+                .sum::<u32>();
+        let pane_heights = self
+            .panes
+            .iter()
+            .map(|p| (height as u32 * p.height_ratio as u32 / total_ratio) as u16)
+            .collect_vec();
+
+        (height - pane_heights.iter().sum::<u16>(), pane_heights)
+    }
+
+    /// Plot area offset of the column showing `timestamp`. In `Fixed` mode a timestamp without a
+    /// candle still has its interval slot.
+    fn column_x(
+        &self,
+        columns: &[Column],
+        start_timestamp: i64,
+        width: u16,
+        scale: Scale,
+        timestamp: i64,
+    ) -> Option<u16> {
+        if let Some(index) = Column::find(columns, timestamp) {
+            let column = &columns[index];
+            return Some(column.x + (column.width - 1) / 2);
         }
 
-        let global_min = self.candles.iter().map(|c| c.low).min().unwrap();
-        let global_max = self.candles.iter().map(|c| c.high).max().unwrap();
+        match self.fit_mode {
+            ChartFitMode::Fixed => {
+                let step = self.interval_millis() * scale.merge;
+                let slot = (timestamp - start_timestamp).div_euclid(step);
+                let x = scale.offset(width) as i64
+                    + slot * scale.column_width as i64
+                    + (scale.column_width as i64 - 1) / 2;
+                (0..width as i64).contains(&x).then_some(x as u16)
+            }
+            ChartFitMode::Fit => None,
+        }
+    }
 
-        let y_axis_width: u16 = if self.show_y_axis {
-            YAxis::estimated_width(self.numeric.clone(), global_min, global_max)
-        } else {
-            0
+    /// Draw the cursor line through the empty cells of every pane
+    fn render_cursor(&self, x: u16, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            if let Some(cell) = buf.cell_mut((area.x + x, y))
+                && cell.symbol() == UNICODE_VOID
+            {
+                cell.set_symbol(UNICODE_CURSOR)
+                    .set_style(Style::default().fg(self.cursor_color));
+            }
+        }
+    }
+
+    /// Dashed line across the empty cells of `area` at the latest close, tagged on the y axes
+    fn render_last_price(&self, y_axis: &YAxis, area: Rect, y_axis_width: u16, buf: &mut Buffer) {
+        let Some(latest) = self.candles.last() else {
+            return;
         };
-        let x_axis_height: u16 = if self.show_x_axis { 3 } else { 0 };
-        
-        if area.width <= y_axis_width || area.height <= x_axis_height {
+        let Some(row) = y_axis.visible_row(latest.close) else {
             return;
+        };
+        let (color, _) = self.candle_colors(latest);
+        let y = area.y + row;
+        for x in area.left()..area.right() {
+            if let Some(cell) = buf.cell_mut((x, y))
+                && cell.symbol() == UNICODE_VOID
+            {
+                cell.set_symbol(UNICODE_LAST_PRICE)
+                    .set_style(Style::default().fg(color));
+            }
         }
 
-        let chart_width = area.width - y_axis_width;
-        let chart_width_usize = chart_width as usize;
-
-        // with first/last dummies
-        let first_timestamp = self.candles.first().unwrap().timestamp;
-        let last_timestamp = self.candles.last().unwrap().timestamp;
+        let label = y_axis.label(latest.close);
+        let style = Style::default().fg(Color::Black).bg(color);
+        self.render_y_axis_tag(label.trim_start(), y, style, area, y_axis_width, buf);
+    }
 
-        let mut candles = Vec::new();
-        for i in (1..=(chart_width as i64 - 1)).rev() {
-            candles.push(
-                Candle::new(
-                    first_timestamp - i * self.interval as i64 * 1000,
-                    0.,
-                    0.,
-                    0.,
-                    0.,
-                )
-                .unwrap(),
-            );
+    /// Write `text` on the y axes at row `y`, over the tick labels between the axis line and the
+    /// outer edge
+    fn render_y_axis_tag(
+        &self,
+        text: &str,
+        y: u16,
+        style: Style,
+        area: Rect,
+        y_axis_width: u16,
+        buf: &mut Buffer,
+    ) {
+        if !self.show_y_axis {
+            return;
         }
-        candles.extend(self.candles.clone());
-        for i in 1..=(chart_width as i64 - 1) {
-            candles.push(
-                Candle::new(
-                    last_timestamp + i * self.interval as i64 * 1000,
-                    0.,
-                    0.,
-                    0.,
-                    0.,
-                )
-                .unwrap(),
-            );
+        let tag = format!(" {} ", text);
+        let tag_width = tag.chars().count() as u16;
+        let max_width = y_axis_width.saturating_sub(2);
+        // a shorter tag must not leave parts of the tick label behind
+        let blank = " ".repeat(max_width as usize);
+        if self.y_axis_position.is_left() {
+            let start = area.x.saturating_sub(y_axis_width);
+            let x = max(area.x.saturating_sub(2 + tag_width), start);
+            buf.set_string(start, y, &blank, Style::default());
+            buf.set_stringn(x, y, &tag, max_width as usize, style);
         }
+        if self.y_axis_position.is_right() {
+            buf.set_string(area.right() + 2, y, &blank, Style::default());
+            buf.set_stringn(area.right() + 2, y, &tag, max_width as usize, style);
+        }
+    }
 
-        let chart_end_timestamp = state.cursor_timestamp.unwrap_or(last_timestamp);
-        let chart_start_timestamp =
-            chart_end_timestamp - self.interval as i64 * 1000 * (chart_width_usize as i64 - 1);
-        let rendered_candles = candles
-            .iter()
-            .filter(|c| c.timestamp >= chart_start_timestamp && c.timestamp <= chart_end_timestamp)
-            .collect_vec();
-
-        state.set_info(CandleStikcChartInfo::new(
-            candles[chart_width_usize - 1].timestamp,
-            candles.last().unwrap().timestamp,
-            self.interval,
-            last_timestamp,
-            rendered_candles.first().unwrap().timestamp < first_timestamp,
-        ));
+    /// Numeric to estimate the price axis width with. An automatic scale gets the decimals of the
+    /// narrowest range that can be visible: a single candle or a move between two closes.
+    fn estimation_numeric(&self, price_height: u16) -> Numeric {
+        if !self.numeric.is_auto() {
+            return self.numeric.clone();
+        }
 
-        let y_min = rendered_candles
+        let finest_step = self
+            .candles
             .iter()
-            .filter(|c| c.timestamp >= first_timestamp && c.timestamp <= last_timestamp)
-            .map(|c| c.low)
+            .map(|c| c.high - c.low)
+            .chain(
+                self.candles
+                    .iter()
+                    .tuple_windows()
+                    .map(|(a, b)| OrderedFloat::from((b.close - a.close).abs())),
+            )
+            .filter(|range| *range > Float::default())
             .min()
-            .unwrap();
-        let y_max = rendered_candles
+            .map(|range| range * 4. / max(price_height, 1) as f64)
+            .unwrap_or_default();
+        // a flat window shows the significant digits of its price instead
+        let smallest = self
+            .candles
             .iter()
-            .filter(|c| c.timestamp >= first_timestamp && c.timestamp <= last_timestamp)
-            .map(|c| c.high)
-            .max()
-            .unwrap();
+            .map(|c| OrderedFloat::from(c.low.abs()))
+            .filter(|price| *price > Float::default())
+            .min()
+            .unwrap_or_default();
 
-        let y_axis = YAxis::new(Numeric::default(), area.height - x_axis_height, y_min, y_max);
-        if self.show_y_axis {
-            let rendered_y_axis = y_axis.render();
-            for (y, string) in rendered_y_axis.iter().enumerate() {
-                buf.set_string(area.x, y as u16 + area.y, string, Style::default());
-            }
-        }
+        let stepped = self.numeric.resolve(finest_step, smallest);
+        let flat = self.numeric.resolve(Float::default(), smallest);
+        max_by_key(stepped, flat, |numeric| {
+            numeric.format(smallest).chars().count()
+        })
+    }
 
-        let timestamp_min = rendered_candles.first().unwrap().timestamp;
-        let timestamp_max = rendered_candles.last().unwrap().timestamp;
+    /// Milliseconds between two candles, or 1 between indices
+    fn interval_millis(&self) -> i64 {
+        if self.indexed {
+            1
+        } else {
+            self.interval as i64 * 1000
+        }
+    }
 
-        if self.show_x_axis {
-            let x_axis = XAxis::new(
-                chart_width,
-                timestamp_min,
-                timestamp_max,
-                self.interval,
-                state.cursor_timestamp.is_none(),
-            );
-            let rendered_x_axis = x_axis.render(self.display_timezone);
-            if self.show_y_axis {
-                buf.set_string(area.x + y_axis_width - 2, area.y + area.height - 3, "└──", Style::default());
+    /// Close that percentage and rebased labels are relative to
+    fn relative_base(&self, visible_candles: &[Candle]) -> Option<Float> {
+        let base_timestamp = match self.price_scale {
+            PriceScale::Percentage(base_timestamp) | PriceScale::Rebased(base_timestamp) => {
+                base_timestamp
             }
-            for (y, string) in rendered_x_axis.iter().enumerate() {
-                buf.set_string(
-                    area.x + y_axis_width,
-                    area.y + area.height - 3 + y as u16,
-                    string,
-                    Style::default(),
-                );
+            _ => return None,
+        };
+        let candle = match base_timestamp {
+            Some(timestamp) => {
+                let index = self.candles.partition_point(|c| c.timestamp <= timestamp);
+                &self.candles[index.saturating_sub(1)]
             }
+            None => visible_candles.first()?,
+        };
+        Some(candle.close)
+    }
+
+    fn candle_colors(&self, candle: &Candle) -> (Color, Color) {
+        self.colors(candle.candle_type())
+    }
+
+    fn colors(&self, candle_type: CandleType) -> (Color, Color) {
+        match candle_type {
+            CandleType::Bearish => (self.bearish_color, self.bearish_wick_color),
+            CandleType::Bullish => (self.bullish_color, self.bullish_wick_color),
         }
+    }
 
-        // Calculate candle width and spacing distribution, or merge candles for squashing
-        let (processed_candles, candle_width, extra_spaces, left_offset) = match self.fit_mode {
+    /// Place the visible candles into columns. `Fixed` mode keeps one column per zoomed interval,
+    /// `Fit` mode merges candles when they do not fit or stretches them up to `max_width`.
+    fn layout(
+        &self,
+        candles: Vec<Candle>,
+        start_timestamp: i64,
+        chart_width: u16,
+        scale: Scale,
+    ) -> Vec<Column> {
+        match self.fit_mode {
             ChartFitMode::Fixed => {
-                let data_candles: Vec<Candle> = rendered_candles.iter()
-                    .filter(|c| c.timestamp >= first_timestamp && c.timestamp <= last_timestamp)
-                    .map(|&c| c.clone())
-                    .collect();
-                (data_candles, 1u16, 0u16, 0u16)
-            },
+                let step = self.interval_millis() * scale.merge;
+                let slot = |candle: &Candle| (candle.timestamp - start_timestamp).div_euclid(step);
+                let offset = scale.offset(chart_width);
+                candles
+                    .chunk_by(|a, b| slot(a) == slot(b))
+                    .map(|chunk| Column {
+                        candle: Candle::merge(chunk).unwrap(),
+                        end_timestamp: chunk.last().unwrap().timestamp,
+                        x: offset + slot(&chunk[0]) as u16 * scale.column_width,
+                        width: scale.column_width,
+                    })
+                    .collect()
+            }
             ChartFitMode::Fit => {
-                let data_candles: Vec<Candle> = rendered_candles.iter()
-                    .filter(|c| c.timestamp >= first_timestamp && c.timestamp <= last_timestamp)
-                    .map(|&c| c.clone())
-                    .collect();
-                    
-                if data_candles.is_empty() {
-                    (data_candles, 1u16, 0u16, 0u16)
-                } else if data_candles.len() > chart_width as usize {
+                if candles.is_empty() {
+                    return Vec::new();
+                }
+
+                if candles.len() > chart_width as usize {
                     // Squashing: merge candles
-                    let merge_ratio = (data_candles.len() + chart_width as usize - 1) / chart_width as usize; // Ceiling division
-                    let mut merged_candles = Vec::new();
-                    
-                    for chunk in data_candles.chunks(merge_ratio) {
-                        if !chunk.is_empty() {
-                            // Create merged candle: first open, last close, min low, max high
-                            let merged = Candle::new(
-                                chunk[0].timestamp, // Use first timestamp
-                                chunk[0].open.into(),
-                                chunk.iter().map(|c| c.high).max().unwrap().into(),
-                                chunk.iter().map(|c| c.low).min().unwrap().into(),
-                                chunk[chunk.len() - 1].close.into()
-                            ).unwrap();
-                            merged_candles.push(merged);
+                    let merge_ratio = candles.len().div_ceil(chart_width as usize);
+                    return candles
+                        .chunks(merge_ratio)
+                        .enumerate()
+                        .map(|(i, chunk)| Column {
+                            candle: Candle::merge(chunk).unwrap(),
+                            end_timestamp: chunk.last().unwrap().timestamp,
+                            x: i as u16,
+                            width: 1,
+                        })
+                        .collect();
+                }
+
+                // Stretching: calculate desired width and apply max_width limit
+                let count = candles.len() as u16;
+                let desired_width = chart_width / count;
+                let width = min(desired_width, self.max_width);
+                let used_width = width * count;
+                let (extra_spaces, left_offset) = if width == desired_width {
+                    // Normal stretching with extra spaces
+                    (chart_width.saturating_sub(used_width), 0u16)
+                } else {
+                    // Width was capped, calculate left offset for blank space on the left
+                    (0u16, chart_width.saturating_sub(used_width))
+                };
+
+                // Pre-calculate where extra spaces should go for even distribution
+                let mut space_positions = vec![false; candles.len()];
+                if extra_spaces > 0 && candles.len() > 1 {
+                    let gaps = candles.len() - 1;
+                    for i in 0..min(extra_spaces as usize, gaps) {
+                        // Distribute spaces evenly across gaps using floating point for precision
+                        let position =
+                            ((i as f64 + 0.5) * gaps as f64 / extra_spaces as f64) as usize;
+                        if position < gaps {
+                            space_positions[position] = true;
                         }
                     }
-                    
-                    (merged_candles, 1u16, 0u16, 0u16)
-                } else {
-                    // Stretching: calculate desired width and apply max_width limit
-                    let desired_width = chart_width / data_candles.len() as u16;
-                    let actual_width = std::cmp::min(desired_width, self.max_width);
-                    let used_width = actual_width * data_candles.len() as u16;
-                    let (extra_spaces, left_offset) = if actual_width == desired_width {
-                        // Normal stretching with extra spaces
-                        (chart_width.saturating_sub(used_width), 0u16)
-                    } else {
-                        // Width was capped, calculate left offset for blank space on the left
-                        let blank_space = chart_width.saturating_sub(used_width);
-                        (0u16, blank_space)
-                    };
-                    
-                    (data_candles, actual_width, extra_spaces, left_offset)
                 }
+
+                let mut x = left_offset;
+                candles
+                    .into_iter()
+                    .zip(space_positions)
+                    .map(|(candle, space)| {
+                        let column = Column {
+                            end_timestamp: candle.timestamp,
+                            candle,
+                            x,
+                            width,
+                        };
+                        x += width + space as u16;
+                        column
+                    })
+                    .collect()
             }
-        };
-        
-        let mut candle_index = 0;
-        let mut current_x_offset = left_offset; // Start with left offset for blank space
-        
-        // Pre-calculate where extra spaces should go for even distribution
-        let mut space_positions = vec![false; processed_candles.len()];
-        if extra_spaces > 0 && processed_candles.len() > 1 {
-            let gaps = processed_candles.len() - 1;
-            for i in 0..extra_spaces as usize {
-                if i < gaps {
-                    // Distribute spaces evenly across gaps using floating point for precision
-                    let position = ((i as f64 + 0.5) * gaps as f64 / extra_spaces as f64) as usize;
-                    if position < space_positions.len() - 1 {
-                        space_positions[position] = true;
-                    }
-                }
+        }
+    }
+
+    /// Write the labels of `y_axis` on the sides of `plot_area` picked by the y axis position
+    fn render_y_axes(&self, y_axis: &YAxis, plot_area: Rect, width: u16, buf: &mut Buffer) {
+        if self.y_axis_position.is_left() {
+            render_y_axis(y_axis, plot_area.x - width, plot_area.y, width, buf);
+        }
+        if self.y_axis_position.is_right() {
+            render_right_y_axis(y_axis, plot_area.right(), plot_area.y, width, buf);
+        }
+    }
+
+    /// Draw the columns in the chart style. `candles` is the series the columns were laid out
+    /// from, which may be scrolled beyond the first column.
+    fn render_candles(
+        &self,
+        candles: &[Candle],
+        columns: &[Column],
+        y_axis: &YAxis,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        match self.chart_style {
+            ChartStyle::Line => return self.render_close_line(columns, y_axis, area, buf),
+            ChartStyle::Area => return self.render_close_area(columns, y_axis, area, buf),
+            ChartStyle::PointAndFigure => {
+                return self.render_point_and_figure(columns, y_axis, area, buf);
             }
+            ChartStyle::Kagi => return self.render_kagi(columns, y_axis, area, buf),
+            _ => {}
         }
-        
-        for candle in processed_candles.iter() {
-            let (body_color, wick_color) = match if candle.open <= candle.close {
-                CandleType::Bullish
+
+        // close of the candle before the first column, which may be scrolled out of view
+        let mut previous_close = columns.first().and_then(|first| {
+            let index = candles.partition_point(|c| c.timestamp < first.candle.timestamp);
+            index.checked_sub(1).map(|i| candles[i].close)
+        });
+
+        for column in columns {
+            let (body_color, wick_color) = if self.chart_style == ChartStyle::HollowCandles {
+                let previous_close = previous_close.unwrap_or(column.candle.open);
+                self.colors(if column.candle.close >= previous_close {
+                    CandleType::Bullish
+                } else {
+                    CandleType::Bearish
+                })
             } else {
-                CandleType::Bearish
-            } {
-                CandleType::Bearish => (self.bearish_color, self.bearish_wick_color),
-                CandleType::Bullish => (self.bullish_color, self.bullish_wick_color),
+                self.candle_colors(&column.candle)
             };
+            previous_close = Some(column.candle.close);
 
-            if candle_width == 1 && extra_spaces == 0 {
-                // Use normal rendering
-                let (_, rendered) = candle.render(&y_axis);
-                for (y, char) in rendered.iter().enumerate() {
-                    let cell_x = left_offset + candle_index as u16 + y_axis_width + area.x;
-                    let cell_y = y as u16 + area.y;
-                    if cell_x < area.x + area.width && let Some(cell) = buf.cell_mut((cell_x, cell_y)) {
+            let rendered = if self.chart_style == ChartStyle::OhlcBars {
+                column.candle.render_bar(y_axis, column.width)
+            } else if self.chart_style == ChartStyle::HollowCandles {
+                column.candle.render_hollow(y_axis, column.width)
+            } else if column.width == 1 {
+                let (_, rendered) = column.candle.render(y_axis);
+                rendered.into_iter().map(|char| vec![char]).collect_vec()
+            } else {
+                let (_, rendered) = column.candle.render_stretched(y_axis, column.width);
+                rendered
+            };
+
+            // the walls of a hollow body share the wick glyph but belong to the body
+            let body_rows = match self.chart_style {
+                ChartStyle::HollowCandles => column.candle.body_rows(y_axis),
+                _ => None,
+            };
+
+            for (y, row) in rendered.iter().enumerate() {
+                let is_body_row =
+                    body_rows.is_some_and(|(top, bottom)| (top..=bottom).contains(&y));
+                for (dx, char) in row.iter().enumerate() {
+                    let cell_x = area.x + column.x + dx as u16;
+                    let cell_y = area.y + y as u16;
+                    if cell_x < area.right()
+                        && let Some(cell) = buf.cell_mut((cell_x, cell_y))
+                    {
                         // Determine if this character is a wick or body
-                        let is_wick = matches!(*char, UNICODE_WICK | UNICODE_HALF_WICK_BOTTOM | UNICODE_HALF_WICK_TOP);
+                        let is_wick = matches!(
+                            *char,
+                            UNICODE_WICK
+                                | UNICODE_RIGHT_EIGHTH_BLOCK
+                                | UNICODE_LEFT_EIGHTH_BLOCK
+                                | UNICODE_HALF_WICK_BOTTOM
+                                | UNICODE_HALF_WICK_TOP
+                        );
+                        let is_wick =
+                            is_wick && !is_body_row && self.chart_style != ChartStyle::OhlcBars;
                         let color = if is_wick { wick_color } else { body_color };
-                        
-                        cell.set_symbol(char)
-                            .set_style(Style::default().fg(color));
+
+                        cell.set_symbol(char).set_style(Style::default().fg(color));
                     }
                 }
-            } else {
-                // Use stretched rendering with pre-calculated spacing
-                let (_, stretched_rendered) = if candle_width > 1 {
-                    candle.render_stretched(&y_axis, candle_width)
-                } else {
-                    candle.render_stretched(&y_axis, 1)
-                };
-                
-                for (y, row) in stretched_rendered.iter().enumerate() {
-                    for (dx, char) in row.iter().enumerate() {
-                        let cell_x = current_x_offset + dx as u16 + y_axis_width + area.x;
-                        let cell_y = y as u16 + area.y;
-                        if cell_x < area.x + area.width && let Some(cell) = buf.cell_mut((cell_x, cell_y)) {
-                            // Determine if this character is a wick or body
-                            let is_wick = matches!(*char, UNICODE_WICK | UNICODE_RIGHT_EIGHTH_BLOCK | UNICODE_LEFT_EIGHTH_BLOCK | UNICODE_HALF_WICK_BOTTOM | UNICODE_HALF_WICK_TOP);
-                            let color = if is_wick { wick_color } else { body_color };
-                            
-                            cell.set_symbol(char)
-                                .set_style(Style::default().fg(color));
-                        }
+            }
+        }
+    }
+
+    /// Color of the close series: bullish when the visible columns close at or above their first
+    /// open
+    fn close_series_color(&self, columns: &[Column]) -> Color {
+        let candles = columns.iter().map(|c| c.candle.clone()).collect_vec();
+        match Candle::merge(&candles) {
+            Some(candle) => self.candle_colors(&candle).0,
+            None => self.bullish_color,
+        }
+    }
+
+    fn render_close_line(&self, columns: &[Column], y_axis: &YAxis, area: Rect, buf: &mut Buffer) {
+        let closes = columns
+            .iter()
+            .map(|c| (c.candle.timestamp, *c.candle.close))
+            .collect_vec();
+        let mut canvas = BrailleCanvas::new(area.width, area.height);
+        Overlay::new(closes, self.close_series_color(columns)).render(columns, y_axis, &mut canvas);
+        canvas.render(area, buf);
+    }
+
+    fn render_close_area(&self, columns: &[Column], y_axis: &YAxis, area: Rect, buf: &mut Buffer) {
+        let style = Style::default().fg(self.close_series_color(columns));
+        for (index, column) in columns.iter().enumerate() {
+            // fill the gap up to the next column so the area stays continuous
+            let width = columns
+                .get(index + 1)
+                .map_or(column.width, |next| next.x - column.x);
+            let close = *y_axis.calc_y(column.candle.close);
+            for y in 0..y_axis.height() {
+                // eighths of the row below the close, counted from the bottom of the area
+                let filled = close - (y_axis.height() - 1 - y) as f64;
+                let eighths = (filled.clamp(0., 1.) * 8.).round() as usize;
+                if eighths == 0 {
+                    continue;
+                }
+                for dx in 0..width {
+                    let cell_x = area.x + column.x + dx;
+                    if cell_x < area.right()
+                        && let Some(cell) = buf.cell_mut((cell_x, area.y + y))
+                    {
+                        cell.set_symbol(UNICODE_LOWER_BLOCKS[eighths])
+                            .set_style(style);
                     }
                 }
-                
-                // Move to next position
-                current_x_offset += candle_width;
-                
-                // Add extra space if this position is marked for it
-                if candle_index < space_positions.len() && space_positions[candle_index] {
-                    current_x_offset += 1;
+            }
+        }
+    }
+
+    /// Draw X or O in the middle cell of each column, in the rows whose middle lies between its
+    /// low and high
+    fn render_point_and_figure(
+        &self,
+        columns: &[Column],
+        y_axis: &YAxis,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        for column in columns {
+            let candle = &column.candle;
+            let (symbol, color) = match candle.candle_type() {
+                CandleType::Bullish => (UNICODE_X_BOX, self.bullish_color),
+                CandleType::Bearish => (UNICODE_O_BOX, self.bearish_color),
+            };
+            let (low, high) = (*y_axis.calc_y(candle.low), *y_axis.calc_y(candle.high));
+            let x = area.x + column.x + (column.width - 1) / 2;
+            for y in 0..y_axis.height() {
+                let middle = (y_axis.height() - y) as f64 - 0.5;
+                if (low..=high).contains(&middle)
+                    && let Some(cell) = buf.cell_mut((x, area.y + y))
+                {
+                    cell.set_symbol(symbol)
+                        .set_style(Style::default().fg(color));
                 }
             }
-            candle_index += 1;
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use ratatui::{
-        buffer::{Buffer, Cell},
-        layout::Rect,
-        style::{Style, Stylize},
-        widgets::StatefulWidget,
-    };
+    /// Draw each column as a vertical line in its middle cell, joined to the next column at
+    /// the high or low they share. A line turning yang or yin changes weight and color at the
+    /// previous peak or trough.
+    fn render_kagi(&self, columns: &[Column], y_axis: &YAxis, area: Rect, buf: &mut Buffer) {
+        const UP: u8 = 1;
+        const DOWN: u8 = 2;
+        const LEFT: u8 = 4;
+        const RIGHT: u8 = 8;
 
-    use crate::{Candle, CandleStickChart, CandleStickChartState, Interval};
+        // joined directions and candle type of every cell, rows from the top
+        let mut cells = vec![(0u8, None); area.width as usize * area.height as usize];
+        let mut join = |x: u16, y: u16, directions: u8, candle_type: CandleType| {
+            if x < area.width && y < area.height {
+                let cell = &mut cells[y as usize * area.width as usize + x as usize];
+                cell.0 |= directions;
+                cell.1 = Some(candle_type);
+            }
+        };
+        let middle = |column: &Column| column.x + (column.width - 1) / 2;
+        // a line changes type where it crosses the start of the line before, which is the
+        // previous peak or trough
+        let row_type = |i: usize, y: u16| {
+            let candle_type = columns[i].candle.candle_type();
+            let Some(previous) = i.checked_sub(1).map(|i| &columns[i].candle) else {
+                return candle_type;
+            };
+            match (previous.candle_type(), candle_type) {
+                (CandleType::Bearish, CandleType::Bullish) if y >= y_axis.row(previous.high) => {
+                    CandleType::Bearish
+                }
+                (CandleType::Bullish, CandleType::Bearish) if y <= y_axis.row(previous.low) => {
+                    CandleType::Bullish
+                }
+                _ => candle_type,
+            }
+        };
 
-    fn render(widget: CandleStickChart, width: u16, height: u16) -> Buffer {
-        let area = Rect::new(0, 0, width, height);
-        let cell = Cell::new("x");
-        let mut buffer = Buffer::filled(area, cell);
-        widget.render(area, &mut buffer, &mut CandleStickChartState::default());
+        for (i, column) in columns.iter().enumerate() {
+            let candle = &column.candle;
+            let (top, bottom) = (y_axis.row(candle.high), y_axis.row(candle.low));
+            let x = middle(column);
+            for y in top..=bottom {
+                let up = if y > top { UP } else { 0 };
+                let down = if y < bottom { DOWN } else { 0 };
+                join(x, y, up | down, row_type(i, y));
+            }
+
+            // a line turns where the next one starts, at their shared high or low
+            let Some(next) = columns.get(i + 1) else {
+                continue;
+            };
+            let y = if next.candle.high == candle.high {
+                top
+            } else if next.candle.low == candle.low {
+                bottom
+            } else {
+                continue;
+            };
+            let next_x = middle(next);
+            join(x, y, RIGHT, row_type(i, y));
+            for x in x + 1..next_x {
+                join(x, y, LEFT | RIGHT, row_type(i, y));
+            }
+            join(next_x, y, LEFT, row_type(i + 1, y));
+        }
+
+        for (i, (directions, candle_type)) in cells.into_iter().enumerate() {
+            let Some(candle_type) = candle_type else {
+                continue;
+            };
+            let (symbols, color) = match candle_type {
+                CandleType::Bullish => (UNICODE_KAGI_HEAVY, self.bullish_color),
+                CandleType::Bearish => (UNICODE_KAGI_LIGHT, self.bearish_color),
+            };
+            let x = area.x + (i % area.width as usize) as u16;
+            let y = area.y + (i / area.width as usize) as u16;
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_symbol(symbols[directions as usize])
+                    .set_style(Style::default().fg(color));
+            }
+        }
+    }
+
+    fn render_volume(&self, columns: &[Column], y_axis: &YAxis, area: Rect, buf: &mut Buffer) {
+        for column in columns {
+            let Some(volume) = column.candle.volume else {
+                continue;
+            };
+            let (color, _) = self.candle_colors(&column.candle);
+            let bar_height = *y_axis.calc_y(volume);
+
+            for y in 0..area.height {
+                let fill = (bar_height - y as f64).clamp(0., 1.);
+                let mut eighths = (fill * 8.).round() as usize;
+                if y == 0 && volume > Float::default() {
+                    // keep tiny volumes visible
+                    eighths = max(eighths, 1);
+                }
+
+                let cell_y = area.bottom() - 1 - y;
+                for dx in 0..column.width {
+                    let cell_x = area.x + column.x + dx;
+                    if cell_x < area.right()
+                        && let Some(cell) = buf.cell_mut((cell_x, cell_y))
+                    {
+                        cell.set_symbol(UNICODE_LOWER_BLOCKS[eighths])
+                            .set_style(Style::default().fg(color));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Write y axis labels right-aligned to the axis width so panes with different label widths line up.
+fn render_y_axis(y_axis: &YAxis, x: u16, y: u16, width: u16, buf: &mut Buffer) {
+    for (dy, string) in y_axis.render().iter().enumerate() {
+        let offset = width.saturating_sub(string.chars().count() as u16);
+        buf.set_string(x + offset, y + dy as u16, string, Style::default());
+    }
+}
+
+/// Write y axis labels left-aligned and padded to the axis width, for an axis right of the plot
+fn render_right_y_axis(y_axis: &YAxis, x: u16, y: u16, width: u16, buf: &mut Buffer) {
+    for (dy, string) in y_axis.render_right().iter().enumerate() {
+        let string = format!("{:<1$}", string, width as usize);
+        buf.set_string(x, y + dy as u16, string, Style::default());
+    }
+}
+
+/// Highlight the cursor time below the x axis line, blanking the labels it partially covers
+fn render_cursor_label(label: String, x: u16, area: Rect, buf: &mut Buffer) {
+    let width = label.chars().count() as u16;
+    if width > area.width {
+        return;
+    }
+
+    let start = area.x + x.saturating_sub(width / 2).min(area.width - width);
+    let end = start + width;
+    let y = area.y + 1;
+    blank_word(buf, (area.left()..start).rev(), y);
+    blank_word(buf, end..area.right(), y);
+
+    buf.set_string(area.x + x, area.y, "┴", Style::default());
+    buf.set_string(start, y, label, Style::default().reversed());
+}
+
+/// Blank label cells of row `y` in the order given until the first empty one, removing their
+/// ticks from the axis line above
+fn blank_word(buf: &mut Buffer, xs: impl Iterator<Item = u16>, y: u16) {
+    for x in xs {
+        match buf.cell_mut((x, y)) {
+            Some(cell) if cell.symbol() != UNICODE_VOID => {
+                cell.set_symbol(UNICODE_VOID);
+            }
+            _ => break,
+        }
+        if let Some(cell) = buf.cell_mut((x, y - 1))
+            && cell.symbol() == "┴"
+        {
+            cell.set_symbol("─");
+        }
+    }
+}
+
+impl Styled for CandleStickChart {
+    type Item = CandleStickChart;
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn set_style<S: Into<Style>>(self, style: S) -> Self::Item {
+        self.style(style.into())
+    }
+}
+
+impl StatefulWidget for CandleStickChart {
+    type State = CandleStickChartState;
+
+    /// render like:
+    /// |---|-----------------------|
+    /// | y |                       |
+    /// |   |                       |
+    /// | a |                       |
+    /// | x |                       |
+    /// | i |                       |
+    /// | s |       chart data      |
+    /// |   |                       |
+    /// | a |                       |
+    /// | r |                       |
+    /// | e |                       |
+    /// | a |                       |
+    /// |---|-----------------------|
+    /// |   |   volume (optional)   |
+    /// |---|-----------------------|
+    ///     |      x axis area      |
+    ///     |-----------------------|
+    ///
+    ///
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if self.candles.is_empty() {
+            return;
+        }
+
+        let global_min = self.candles.iter().map(|c| c.low).min().unwrap();
+        let global_max = self.candles.iter().map(|c| c.high).max().unwrap();
+        let volume_height = if self.show_volume {
+            self.volume_height
+        } else {
+            0
+        };
+
+        let x_axis_height: u16 = if self.show_x_axis { 3 } else { 0 };
+        if area.height <= x_axis_height + volume_height {
+            return;
+        }
+        let (price_height, pane_heights) =
+            self.split_heights(area.height - x_axis_height - volume_height);
+
+        let y_axis_width: u16 = if self.show_y_axis {
+            let mut width = match self.price_scale {
+                PriceScale::Percentage(_) | PriceScale::Rebased(_) => {
+                    let closes = self.candles.iter().map(|c| c.close);
+                    YAxis::estimated_relative_width(
+                        self.numeric.clone(),
+                        self.price_scale,
+                        (global_min, global_max),
+                        (closes.clone().min().unwrap(), closes.max().unwrap()),
+                    )
+                }
+                _ => YAxis::estimated_width(
+                    self.estimation_numeric(price_height),
+                    global_min,
+                    global_max,
+                ),
+            };
+            if volume_height > 0 {
+                let global_max_volume = self
+                    .candles
+                    .iter()
+                    .filter_map(|c| c.volume)
+                    .max()
+                    .unwrap_or_default();
+                width = max(
+                    width,
+                    YAxis::estimated_width(Numeric::default(), Float::default(), global_max_volume),
+                );
+            }
+            self.panes
+                .iter()
+                .map(|pane| pane.estimated_width())
+                .fold(width, max)
+        } else {
+            0
+        };
+        let left_width = if self.y_axis_position.is_left() {
+            y_axis_width
+        } else {
+            0
+        };
+        let right_width = if self.y_axis_position.is_right() {
+            y_axis_width
+        } else {
+            0
+        };
+        if area.width <= left_width + right_width {
+            return;
+        }
+
+        let chart_width = area.width - left_width - right_width;
+        let interval = self.interval_millis();
+        let scale = state.scale().fit(chart_width);
+        let step = interval * scale.merge;
+        let column_count = scale.columns(chart_width) as i64;
+
+        let first_timestamp = self.candles.first().unwrap().timestamp;
+        let last_timestamp = self.candles.last().unwrap().timestamp;
+
+        // merged columns are aligned to the candle grid so they do not change while scrolling
+        let anchor_timestamp = state
+            .anchor_timestamp
+            .or(state.cursor_timestamp)
+            .unwrap_or(last_timestamp);
+        let last_column_timestamp = anchor_timestamp
+            - (anchor_timestamp - first_timestamp.rem_euclid(interval)).rem_euclid(step);
+        let chart_start_timestamp = last_column_timestamp - step * (column_count - 1);
+        let chart_end_timestamp = last_column_timestamp + step - interval;
+
+        let visible_candles = self
+            .candles
+            .iter()
+            .filter(|c| c.timestamp >= chart_start_timestamp && c.timestamp <= chart_end_timestamp)
+            .cloned()
+            .collect_vec();
+        let price_range = visible_candles
+            .iter()
+            .map(|c| c.low)
+            .min()
+            .zip(visible_candles.iter().map(|c| c.high).max());
+        let columns = self.layout(
+            visible_candles.clone(),
+            chart_start_timestamp,
+            chart_width,
+            scale,
+        );
+
+        state.set_info(CandleStikcChartInfo {
+            cursor_first_timestamp: first_timestamp,
+            // the cursor may scroll until the latest candle reaches the left edge
+            cursor_last_timestamp: last_timestamp + step * (column_count - 1),
+            step,
+            latest_timestamp: last_timestamp,
+            need_previous_candles: chart_start_timestamp < first_timestamp,
+            anchor_timestamp: last_column_timestamp,
+            visible_range: (chart_start_timestamp, chart_end_timestamp),
+            price_range,
+            plot_area: Rect::new(left_width, 0, chart_width, area.height - x_axis_height),
+            column_width: scale.column_width,
+            columns: columns.clone(),
+            timestamps: self.candles.iter().map(|c| c.timestamp).collect(),
+        });
+
+        if visible_candles.is_empty() {
+            return;
+        }
+
+        // Heikin-Ashi candles are drawn in place of the real ones, which stay in the state and
+        // the tooltip
+        let relative_base = self.relative_base(&visible_candles);
+        let heikin_ashi = self
+            .heikin_ashi
+            .then(|| indicators::heikin_ashi(&self.candles));
+        let candles = heikin_ashi.as_deref().unwrap_or(&self.candles);
+        let real_columns = columns;
+        let (visible_candles, columns) = if heikin_ashi.is_some() {
+            let visible_candles = candles
+                .iter()
+                .filter(|c| {
+                    c.timestamp >= chart_start_timestamp && c.timestamp <= chart_end_timestamp
+                })
+                .cloned()
+                .collect_vec();
+            let columns = self.layout(
+                visible_candles.clone(),
+                chart_start_timestamp,
+                chart_width,
+                scale,
+            );
+            (visible_candles, columns)
+        } else {
+            (visible_candles, real_columns.clone())
+        };
+
+        let overlay_values = self
+            .overlays
+            .iter()
+            .flat_map(|o| o.visible_values(chart_start_timestamp, chart_end_timestamp))
+            .collect_vec();
+        let y_min = visible_candles
+            .iter()
+            .map(|c| c.low)
+            .chain(overlay_values.iter().copied())
+            .min()
+            .unwrap();
+        let y_max = visible_candles
+            .iter()
+            .map(|c| c.high)
+            .chain(overlay_values.iter().copied())
+            .max()
+            .unwrap();
+        let (markers_above, markers_below) = marker::stack_heights(&self.markers, &columns);
+        let mut y_axis = YAxis::new(self.numeric.clone(), price_height, y_min, y_max)
+            .scale(self.price_scale)
+            .reserve_rows(markers_above, markers_below)
+            .tick_spacing(self.y_axis_tick_spacing);
+        if let Some(base) = relative_base {
+            y_axis = y_axis.base(base);
+        }
+
+        let max_volume = visible_candles.iter().filter_map(|c| c.volume).max();
+        let volume_axis = match max_volume {
+            Some(max_volume) if volume_height > 0 && max_volume > Float::default() => Some(
+                YAxis::new(
+                    Numeric::default(),
+                    volume_height,
+                    Float::default(),
+                    max_volume,
+                )
+                .tick_spacing(self.y_axis_tick_spacing),
+            ),
+            _ => None,
+        };
+
+        let price_area = Rect::new(area.x + left_width, area.y, chart_width, price_height);
+        if self.show_y_axis {
+            self.render_y_axes(&y_axis, price_area, y_axis_width, buf);
+            if let Some(volume_axis) = &volume_axis {
+                let volume_area = Rect {
+                    y: price_area.bottom(),
+                    height: volume_height,
+                    ..price_area
+                };
+                self.render_y_axes(volume_axis, volume_area, y_axis_width, buf);
+            }
+        }
+
+        let x_axis = XAxis::new(
+            chart_width,
+            chart_start_timestamp,
+            chart_end_timestamp,
+            self.interval,
+            state.is_live(),
+        )
+        .scale(scale)
+        .indexed(self.indexed)
+        .index_timestamps(self.index_timestamps.clone());
+        if self.show_x_axis {
+            let rendered_x_axis = x_axis.render(self.display_timezone);
+            if self.show_y_axis && left_width > 0 {
+                buf.set_string(
+                    price_area.x - 2,
+                    area.y + area.height - 3,
+                    "└──",
+                    Style::default(),
+                );
+            }
+            if self.show_y_axis && right_width > 0 {
+                buf.set_string(
+                    price_area.right(),
+                    area.y + area.height - 3,
+                    "─┘",
+                    Style::default(),
+                );
+            }
+            for (y, string) in rendered_x_axis.iter().enumerate() {
+                buf.set_string(
+                    price_area.x,
+                    area.y + area.height - 3 + y as u16,
+                    string,
+                    Style::default(),
+                );
+            }
+        }
+
+        for band in &self.price_bands {
+            band.render(&y_axis, price_area, buf);
+        }
+        self.render_candles(candles, &columns, &y_axis, price_area, buf);
+        for line in &self.price_lines {
+            line.render(&y_axis, price_area, buf);
+        }
+        let tags = self.price_bands.iter().filter_map(|band| band.tag(&y_axis));
+        let tags = tags.chain(self.price_lines.iter().filter_map(|line| line.tag(&y_axis)));
+        for (row, text, style) in tags {
+            let y = price_area.y + row;
+            self.render_y_axis_tag(&text, y, style, price_area, y_axis_width, buf);
+        }
+        if self.show_last_price && state.is_live() {
+            self.render_last_price(&y_axis, price_area, y_axis_width, buf);
+        }
+
+        if !self.drawings.is_empty() {
+            let mut levels = BrailleCanvas::new(price_area.width, price_area.height);
+            for drawing in &self.drawings {
+                drawing.render_levels(&columns, interval, &y_axis, &mut levels);
+            }
+            levels.render_behind(price_area, buf);
+        }
+        if !self.overlays.is_empty() || !self.drawings.is_empty() {
+            let mut canvas = BrailleCanvas::new(price_area.width, price_area.height);
+            for overlay in &self.overlays {
+                overlay.render(&columns, &y_axis, &mut canvas);
+            }
+            for drawing in &self.drawings {
+                drawing.render(&columns, interval, &y_axis, &mut canvas);
+            }
+            canvas.render(price_area, buf);
+            for drawing in &self.drawings {
+                drawing.render_labels(&columns, interval, &y_axis, price_area, buf);
+            }
+        }
+        marker::render_markers(&self.markers, &columns, &y_axis, price_area, buf);
+
+        if let Some(volume_axis) = &volume_axis {
+            let volume_area = Rect::new(
+                price_area.x,
+                price_area.bottom(),
+                chart_width,
+                volume_height,
+            );
+            self.render_volume(&columns, volume_axis, volume_area, buf);
+        }
+
+        let mut pane_y = price_area.bottom() + volume_height;
+        for (pane, height) in self.panes.iter().zip(pane_heights) {
+            let pane_area = Rect::new(price_area.x, pane_y, chart_width, height);
+            pane_y += height;
+
+            if height == 0 {
+                continue;
+            }
+            let Some(pane_axis) = pane.y_axis(height, chart_start_timestamp, chart_end_timestamp)
+            else {
+                continue;
+            };
+            let pane_axis = pane_axis.tick_spacing(self.y_axis_tick_spacing);
+            if self.show_y_axis {
+                self.render_y_axes(&pane_axis, pane_area, y_axis_width, buf);
+            }
+            pane.render(
+                &columns,
+                &pane_axis,
+                (self.bullish_color, self.bearish_color),
+                pane_area,
+                buf,
+            );
+        }
+
+        let Some(cursor_timestamp) = state.cursor_timestamp else {
+            return;
+        };
+        let Some(cursor_x) = self.column_x(
+            &columns,
+            chart_start_timestamp,
+            chart_width,
+            scale,
+            cursor_timestamp,
+        ) else {
+            return;
+        };
+
+        if self.show_cursor {
+            let plot_area = Rect::new(price_area.x, area.y, chart_width, pane_y - area.y);
+            self.render_cursor(cursor_x, plot_area, buf);
+
+            if self.show_x_axis {
+                let label = x_axis.label(cursor_timestamp, self.display_timezone);
+                let axis_area = Rect::new(price_area.x, area.bottom() - 3, chart_width, 2);
+                render_cursor_label(label, cursor_x, axis_area, buf);
+            }
+        }
+
+        if self.show_tooltip
+            && let Some(index) = Column::find(&real_columns, cursor_timestamp)
+        {
+            Tooltip::new(&real_columns[index].candle, &y_axis.numeric(), self.style).render(
+                self.tooltip_position,
+                price_area.x + cursor_x,
+                price_area,
+                buf,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        buffer::{Buffer, Cell},
+        layout::Rect,
+        style::{Color, Style, Stylize},
+        widgets::StatefulWidget,
+    };
+
+    use crate::{
+        BoxSize, Candle, CandleStickChart, CandleStickChartState, ChartFitMode, ChartStyle,
+        Drawing, Interval, Kagi, Marker, MarkerPosition, MouseEvent, MouseEventKind, Numeric,
+        Overlay, Pane, PointAndFigure, PriceBand, PriceLine, PriceOnlyChart, PriceScale, Renko,
+        YAxisPosition,
+    };
+
+    fn render(widget: CandleStickChart, width: u16, height: u16) -> Buffer {
+        render_with_state(widget, width, height, &mut CandleStickChartState::default())
+    }
+
+    fn render_with_state(
+        widget: CandleStickChart,
+        width: u16,
+        height: u16,
+        state: &mut CandleStickChartState,
+    ) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let cell = Cell::new("x");
+        let mut buffer = Buffer::filled(area, cell);
+        widget.render(area, &mut buffer, state);
         buffer.set_style(area, Style::default().reset());
         buffer
     }
@@ -461,11 +1464,11 @@ mod tests {
         assert_eq!(
             buffer,
             Buffer::with_lines(vec![
-                "     3.000 ├ │",
                 "           │ │",
+                "     2.000 ├ │",
                 "           │ ┃",
                 "           │ │",
-                "     0.600 ├ │",
+                "     0.000 ├ │",
                 "xxxxxxxxxxx└──",
                 "xxxxxxxxxxxxx ",
                 "xxxxxxxxxxxxxx",
@@ -481,11 +1484,11 @@ mod tests {
         assert_eq!(
             buffer,
             Buffer::with_lines(vec![
-                "     3.000 ├ xxxxxxxxxxxxxxxx│",
                 "           │ xxxxxxxxxxxxxxxx│",
+                "     2.000 ├ xxxxxxxxxxxxxxxx│",
                 "           │ xxxxxxxxxxxxxxxx┃",
                 "           │ xxxxxxxxxxxxxxxx│",
-                "     0.600 ├ xxxxxxxxxxxxxxxx│",
+                "     0.000 ├ xxxxxxxxxxxxxxxx│",
                 "xxxxxxxxxxx└─────────────────┴",
                 "xxxxxxxxxxxxx*1970/01/01 00:00",
                 "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
@@ -504,11 +1507,11 @@ mod tests {
         assert_eq!(
             buffer,
             Buffer::with_lines(vec![
-                "     4.200 ├ xxx ╽┃",
+                "     4.000 ├ xxx ╽┃",
                 "           │ xxx│┃┃",
-                "           │ xxx│╹╿",
+                "     2.000 ├ xxx│╹╿",
                 "           │ xxx│  ",
-                "     0.840 ├ xxx│  ",
+                "     0.000 ├ xxx│  ",
                 "xxxxxxxxxxx└──────┴",
                 "xxxxxxxxxxxxx*00:02",
                 "xxxxxxxxxxxxxxxxxxx",
@@ -529,11 +1532,11 @@ mod tests {
         assert_eq!(
             buffer,
             Buffer::with_lines(vec![
-                "     5.200 ├ x ╷  │",
+                "     5.000 ├ x ╷  │",
                 "           │ x ╽┃││",
                 "           │ x│┃╿│┃",
                 "           │ x┃ ╵││",
-                "     1.040 ├ x│   ╵",
+                "     0.000 ├ x│   ╵",
                 "xxxxxxxxxxx└──────┴",
                 "xxxxxxxxxxxxx*00:04",
                 "xxxxxxxxxxxxxxxxxxx",
@@ -551,11 +1554,11 @@ mod tests {
         assert_eq!(
             buffer,
             Buffer::with_lines(vec![
-                "     5.200 ├ x xxx│",
+                "     5.000 ├ x xxx│",
                 "           │ x xxx│",
                 "           │ x│xxx┃",
                 "           │ x┃xxx│",
-                "     1.040 ├ x│xxx╵",
+                "     0.000 ├ x│xxx╵",
                 "xxxxxxxxxxx└──────┴",
                 "xxxxxxxxxxxxx*00:04",
                 "xxxxxxxxxxxxxxxxxxx",
@@ -578,7 +1581,7 @@ mod tests {
                 "           │ │  ",
                 "           │ │ ╻",
                 "           │ │  ",
-                "   200.000 ├ │╻ ",
+                "     0.000 ├ │╻ ",
                 "xxxxxxxxxxx└────",
                 "xxxxxxxxxxxxx   ",
                 "xxxxxxxxxxxxxxxx",
@@ -601,11 +1604,1286 @@ mod tests {
                 "           │ │  ",
                 "           │ │╽┃",
                 "           │ │╵╹",
-                "   200.000 ├ │  ",
+                "     0.000 ├ │  ",
                 "xxxxxxxxxxx└────",
                 "xxxxxxxxxxxxx   ",
                 "xxxxxxxxxxxxxxxx",
             ])
         );
     }
+
+    #[test]
+    fn simple_candles_with_volume() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1)
+                    .unwrap()
+                    .with_volume(10.0),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9)
+                    .unwrap()
+                    .with_volume(40.0),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3)
+                    .unwrap()
+                    .with_volume(25.0),
+            ])
+            .show_volume(true)
+            .volume_height(2);
+        let buffer = render(widget, 19, 10);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ╽┃",
+                "           │ xxx│┃┃",
+                "     2.000 ├ xxx│╹╿",
+                "           │ xxx│  ",
+                "     0.000 ├ xxx│  ",
+                "    40.000 ├ xxx █▂",
+                "     0.000 ├ xxx▄██",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn stretched_candles_with_volume() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1)
+                    .unwrap()
+                    .with_volume(10.0),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9)
+                    .unwrap()
+                    .with_volume(40.0),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3)
+                    .unwrap()
+                    .with_volume(25.0),
+            ])
+            .fit_mode(ChartFitMode::Fit)
+            .show_volume(true)
+            .volume_height(2);
+        let buffer = render(widget, 19, 10);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├   ▐▌▐▌",
+                "           │ ▕▏▐▌▐▌",
+                "     2.000 ├ ▕▏▐▌▐▌",
+                "           │ ▕▏    ",
+                "     0.000 ├ ▕▏    ",
+                "    40.000 ├   ██▂▂",
+                "     0.000 ├ ▄▄████",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn simple_candles_with_overlay() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            ])
+            .overlay(Overlay::new(
+                vec![(0, 1.0), (60000, 2.0), (120000, 3.0)],
+                Color::Reset,
+            ));
+        let buffer = render(widget, 19, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ╽┃",
+                "           │ xxx│⢀⠆",
+                "     2.000 ├ xxx│⡎╿",
+                "           │ xxx⡜  ",
+                "     0.000 ├ xxx│  ",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn stretched_candles_with_overlay() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            ])
+            .fit_mode(ChartFitMode::Fit)
+            .overlay(Overlay::new(
+                vec![(0, 1.0), (60000, 2.0), (120000, 3.0)],
+                Color::Reset,
+            ));
+        let buffer = render(widget, 19, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├   ▐▌▐▌",
+                "           │ ▕▏▐⢀⠔▌",
+                "     2.000 ├ ▕▏⡠⠊▐▌",
+                "           │ ⢀⠜    ",
+                "     0.000 ├ ▕▏    ",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn simple_candles_with_panes() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            ])
+            .price_height_ratio(2)
+            .pane(
+                Pane::new()
+                    .line(Overlay::new(
+                        vec![(0, 20.0), (60000, 80.0), (120000, 50.0)],
+                        Color::Reset,
+                    ))
+                    .range(0., 100.)
+                    .guide_lines(vec![30., 70.]),
+            )
+            .pane(Pane::new().histogram(vec![(0, -1.0), (60000, 2.0), (120000, 1.0)]));
+        let buffer = render(widget, 19, 11);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ╽┃",
+                "           │ xxx│┃╿",
+                "     2.000 ├ xxx┃ ╵",
+                "     0.000 ├ xxx│  ",
+                "   100.000 ├ ┈┈┈⢀⠦⡀",
+                "     0.000 ├ ┈┈┈⠎┈┈",
+                "     2.000 ├ xxxx⣿⣤",
+                "     0.000 ├ xxx⣶⠛⠛",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn cursor_with_tooltip() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ]);
+        let mut state = CandleStickChartState {
+            cursor_timestamp: Some(60000),
+            ..Default::default()
+        };
+        let buffer = render_with_state(widget, 32, 10, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxxxxx┌───────┐ ╽",
+                "           │ xxxxxxxx│O 2.100│╷┃",
+                "           │ xxxxxxxx│H 4.200││┃",
+                "     2.000 ├ xxxxxxxx│L 2.100││╹",
+                "           │ xxxxxxxx│C 3.900│┃┊",
+                "           │ xxxxxxxx└───────┘│┊",
+                "     0.000 ├ xxxxxxxxxxxxxxxxx│┊",
+                "xxxxxxxxxxx└───────────────────┴",
+                "xxxxxxxxxxxxx   1970/01/01 00:01",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn heikin_ashi() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            ])
+            .heikin_ashi(true);
+        let mut state = CandleStickChartState {
+            cursor_timestamp: Some(60000),
+            ..Default::default()
+        };
+        // the tooltip shows the real prices of the candle under the cursor
+        let buffer = render_with_state(widget, 32, 10, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxxxxx┌───────┐ │",
+                "           │ xxxxxxxx│O 2.100│╷│",
+                "           │ xxxxxxxx│H 4.200││┃",
+                "     2.000 ├ xxxxxxxx│L 2.100││┃",
+                "           │ xxxxxxxx│C 3.900││╹",
+                "           │ xxxxxxxx└───────┘│┊",
+                "     0.000 ├ xxxxxxxxxxxxxxxxx│┊",
+                "xxxxxxxxxxx└───────────────────┴",
+                "xxxxxxxxxxxxx   1970/01/01 00:01",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+        assert_eq!(state.visible_min_price(), Some(0.0));
+        assert_eq!(state.visible_max_price(), Some(4.2));
+    }
+
+    #[test]
+    fn indexed() {
+        let closes = [10., 12.5, 11.8, 10.9, 9., 11.2, 13.4];
+        let candles = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle::new(i as i64 * 60000, c, c, c, c).unwrap())
+            .collect::<Vec<_>>();
+        let bricks = Renko::new(BoxSize::Fixed(1.)).compute(&candles);
+        let widget = CandleStickChart::new(Interval::OneDay)
+            .candles(bricks)
+            .indexed(true);
+        let mut state = CandleStickChartState::default();
+        let buffer = render_with_state(widget, 24, 8, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "           │ xxxx      ┃",
+                "    12.000 ├ xxxx ┃   ┃╹",
+                "           │ xxxx╻╹╻ ╻╹ ",
+                "    10.000 ├ xxxx┃ ┃╻┃  ",
+                "           │ xxxx   ┃   ",
+                "xxxxxxxxxxx└─────┴─────┴",
+                "xxxxxxxxxxxxx    0    *6",
+                "xxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+        assert_eq!(state.cursor_timestamp(), Some(6));
+    }
+
+    #[test]
+    fn kagi() {
+        let candles = [10., 14., 11., 13., 10., 15.]
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle::new(i as i64, c, c, c, c).unwrap())
+            .collect::<Vec<_>>();
+        // yang up to 14 and back to 11, yin from below the trough at 11, yang again from above
+        // the peak at 13
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(Kagi::new(2.).compute(&candles))
+            .chart_style(ChartStyle::Kagi)
+            .indexed(true);
+        let buffer = render(widget.clone(), 20, 9);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "    15.000 ├ xxxxxx╻",
+                "           │ xx┏┓xx┃",
+                "           │ xx┃┃┏┓│",
+                "           │ xx┃┃┃┃│",
+                "           │ xx┃┗┛┃│",
+                "    10.000 ├ xx╹xx└┘",
+                "xxxxxxxxxxx└───┴───┴",
+                "xxxxxxxxxxxxx  0  *4",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let mut state = CandleStickChartState::default();
+        state.zoom_in();
+        let buffer = render_with_state(widget, 24, 9, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "    15.000 ├ xxxxxxxxx╻x",
+                "           │ x┏━┓xxxxx┃x",
+                "           │ x┃x┃x┏━┓x│x",
+                "           │ x┃x┃x┃x┃x│x",
+                "           │ x┃x┗━┛x┃x│x",
+                "    10.000 ├ x╹xxxxx└─┘x",
+                "xxxxxxxxxxx└──┴───────┴─",
+                "xxxxxxxxxxxxx 0      *4 ",
+                "xxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn point_and_figure() {
+        // a candle every ten days from 2024/01/10
+        let candles = [10.5, 13.2, 10., 11., 14.5, 12., 11.2]
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle::new(1704844800000 + i as i64 * 864000000, c, c, c, c).unwrap())
+            .collect::<Vec<_>>();
+        let mut point_and_figure = PointAndFigure::new(1., 2);
+        let widget = CandleStickChart::new(Interval::OneDay)
+            .candles(point_and_figure.compute(&candles))
+            .chart_style(ChartStyle::PointAndFigure)
+            .indexed(true)
+            .index_timestamps(point_and_figure.timestamps());
+        let buffer = render(widget.clone(), 24, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "    15.000 ├ xxxxxxxxxXx",
+                "           │ xxxxxxxXxXO",
+                "           │ xxxxxxxXOXO",
+                "           │ xxxxxxxXOXx",
+                "    10.000 ├ xxxxxxxXOxx",
+                "xxxxxxxxxxx└────────┴───",
+                "xxxxxxxxxxxxx   2024/01 ",
+                "xxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        // wider columns leave room for the label of every month
+        let mut state = CandleStickChartState::default();
+        for _ in 0..4 {
+            state.zoom_in();
+        }
+        let buffer = render_with_state(widget, 40, 8, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "    15.000 ├ xxxxxxxxxxxxxxxxxxxXxxxxxxx",
+                "           │ xxxxxxxxxXxxxxxxxxxXxxxxOxx",
+                "           │ xxxxxxxxxXxxxxOxxxxXxxxxOxx",
+                "           │ xxxxxxxxxXxxxxOxxxxXxxxxxxx",
+                "    10.000 ├ xxxxxxxxxXxxxxOxxxxxxxxxxxx",
+                "xxxxxxxxxxx└──────────┴─────────┴───────",
+                "xxxxxxxxxxxxx      2024/01   2024/02    ",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn query_state() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ]);
+        let mut state = CandleStickChartState::default();
+        assert_eq!(state.cursor_timestamp(), None);
+
+        render_with_state(widget.clone(), 16, 8, &mut state);
+        assert!(state.is_live());
+        assert_eq!(state.cursor_timestamp(), Some(120000));
+        assert_eq!(state.visible_start_timestamp(), Some(0));
+        assert_eq!(state.visible_end_timestamp(), Some(120000));
+        assert_eq!(state.visible_min_price(), Some(0.0));
+        assert_eq!(state.visible_max_price(), Some(4.2));
+
+        state.try_move_backward();
+        state.try_move_backward();
+        render_with_state(widget, 16, 8, &mut state);
+        assert!(!state.is_live());
+        assert_eq!(state.cursor_timestamp(), Some(0));
+        assert_eq!(state.visible_start_timestamp(), Some(-120000));
+        assert_eq!(state.visible_end_timestamp(), Some(0));
+        assert_eq!(state.visible_min_price(), Some(0.0));
+        assert_eq!(state.visible_max_price(), Some(3.0));
+    }
+
+    #[test]
+    fn zoomed_in_candles() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ]);
+        let mut state = CandleStickChartState::default();
+        state.zoom_in();
+        state.zoom_in();
+        let buffer = render_with_state(widget, 22, 8, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├    ▐█▌▐█▌",
+                "           │  │ ▐█▌▐█▌",
+                "     2.000 ├  │ ▐█▌▐█▌",
+                "           │  │       ",
+                "     0.000 ├  │       ",
+                "xxxxxxxxxxx└────────┴─",
+                "xxxxxxxxxxxxx   *00:02",
+                "xxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn zoomed_in_beyond_plot_width() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ]);
+        // the candle is narrowed to the plot instead of losing its wick
+        let mut state = CandleStickChartState::default();
+        for _ in 0..6 {
+            state.zoom_in();
+        }
+        let buffer = render_with_state(widget.clone(), 18, 10, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ ▐███▌",
+                "           │ ▐███▌",
+                "           │ ▐███▌",
+                "           │ ▐███▌",
+                "           │ ▐███▌",
+                "           │ ▐███▌",
+                "     2.000 ├   │  ",
+                "xxxxxxxxxxx└──────",
+                "xxxxxxxxxxxxx     ",
+                "xxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        // zooming in stops once a candle fills the plot
+        state.reset_zoom();
+        for _ in 0..6 {
+            render_with_state(widget.clone(), 18, 10, &mut state);
+            state.zoom_in();
+        }
+        assert_eq!(state.zoom_level(), 4);
+    }
+
+    #[test]
+    fn zoomed_out_candles() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            Candle::new(180000, 2.3, 3.9, 1.3, 2.0).unwrap(),
+            Candle::new(240000, 2.0, 5.2, 0.9, 3.9).unwrap(),
+        ]);
+        let mut state = CandleStickChartState::default();
+        state.zoom_out();
+        // pairs of candles are merged into one column
+        let buffer = render_with_state(widget, 19, 8, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     5.000 ├ xxx╷ │",
+                "           │ xxx╽┃│",
+                "           │ xxx┃┃┃",
+                "           │ xxx┃╿│",
+                "     0.000 ├ xxx│ ╵",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:04",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        state.zoom_out();
+        state.zoom_out();
+        assert_eq!(state.zoom_level(), -3);
+        state.reset_zoom();
+        assert_eq!(state.zoom_level(), 0);
+    }
+
+    #[test]
+    fn mouse_interaction() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ]);
+        let area = Rect::new(0, 0, 19, 8);
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 19, 8, &mut state);
+
+        // the x axis is outside of the plot
+        state.handle_mouse_event(MouseEvent::new(16, 6, MouseEventKind::Down), area);
+        assert!(state.is_live());
+
+        // selecting a candle keeps the view
+        state.handle_mouse_event(MouseEvent::new(16, 2, MouseEventKind::Down), area);
+        render_with_state(widget.clone(), 19, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(0));
+        assert_eq!(state.visible_end_timestamp(), Some(120000));
+        // and keeps it on the following frames
+        render_with_state(widget.clone(), 19, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(0));
+        assert_eq!(state.visible_end_timestamp(), Some(120000));
+
+        state.handle_mouse_event(MouseEvent::new(18, 2, MouseEventKind::Drag), area);
+        state.handle_mouse_event(MouseEvent::new(18, 2, MouseEventKind::Up), area);
+        let buffer = render_with_state(widget.clone(), 19, 8, &mut state);
+        assert_eq!(state.visible_end_timestamp(), Some(0));
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "           │ xxxxx│",
+                "     2.000 ├ xxxxx│",
+                "           │ xxxxx┃",
+                "           │ xxxxx│",
+                "     0.000 ├ xxxxx│",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx 1970 ",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        state.handle_mouse_event(MouseEvent::new(16, 2, MouseEventKind::ScrollUp), area);
+        assert_eq!(state.zoom_level(), 1);
+        state.handle_mouse_event(MouseEvent::new(0, 2, MouseEventKind::ScrollDown), area);
+        assert_eq!(state.zoom_level(), 1);
+    }
+
+    #[test]
+    fn cursor_on_gap() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(180000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ]);
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 16, 8, &mut state);
+
+        // no candle at 120000
+        state.try_move_backward();
+        render_with_state(widget.clone(), 16, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), None);
+
+        state.try_move_backward();
+        render_with_state(widget.clone(), 16, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(60000));
+
+        // past the latest candle
+        state.reset_cursor();
+        state.try_move_forward();
+        render_with_state(widget, 16, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), None);
+    }
+
+    #[test]
+    fn drag_back_to_latest() {
+        let candles = (0..20)
+            .map(|i| Candle::new(i * 60000, 1.0, 2.0, 0.0, 1.5).unwrap())
+            .collect::<Vec<_>>();
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(candles.clone());
+        let area = Rect::new(0, 0, 17, 8);
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 17, 8, &mut state);
+
+        state.handle_mouse_event(MouseEvent::new(14, 2, MouseEventKind::Down), area);
+        state.handle_mouse_event(MouseEvent::new(16, 2, MouseEventKind::Drag), area);
+        state.handle_mouse_event(MouseEvent::new(16, 2, MouseEventKind::Up), area);
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert!(!state.is_live());
+        assert_eq!(state.visible_end_timestamp(), Some(17 * 60000));
+
+        state.handle_mouse_event(MouseEvent::new(16, 2, MouseEventKind::Down), area);
+        state.handle_mouse_event(MouseEvent::new(14, 2, MouseEventKind::Drag), area);
+        state.handle_mouse_event(MouseEvent::new(14, 2, MouseEventKind::Up), area);
+        render_with_state(widget, 17, 8, &mut state);
+        assert!(state.is_live());
+
+        // new candles are followed again
+        let candles = [
+            candles,
+            vec![Candle::new(20 * 60000, 1.0, 2.0, 0.0, 1.5).unwrap()],
+        ]
+        .concat();
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(candles);
+        render_with_state(widget, 17, 8, &mut state);
+        assert_eq!(state.visible_end_timestamp(), Some(20 * 60000));
+        assert_eq!(state.cursor_timestamp(), Some(20 * 60000));
+    }
+
+    #[test]
+    fn navigation() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(
+            (0..20)
+                .map(|i| Candle::new(i * 60000, 1.0, 2.0, 0.0, 1.5).unwrap())
+                .collect(),
+        );
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert_eq!(state.visible_start_timestamp(), Some(16 * 60000));
+
+        state.page_backward();
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(15 * 60000));
+        assert_eq!(state.visible_end_timestamp(), Some(15 * 60000));
+
+        state.jump_to_oldest();
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(0));
+        assert_eq!(state.visible_start_timestamp(), Some(0));
+
+        state.page_forward();
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(4 * 60000));
+        assert_eq!(state.visible_start_timestamp(), Some(4 * 60000));
+
+        state.jump_to(10 * 60000 - 1);
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(10 * 60000));
+        assert_eq!(state.visible_end_timestamp(), Some(10 * 60000));
+
+        state.jump_to(i64::MIN);
+        render_with_state(widget.clone(), 17, 8, &mut state);
+        assert_eq!(state.cursor_timestamp(), Some(0));
+
+        state.jump_to_latest();
+        render_with_state(widget, 17, 8, &mut state);
+        assert!(state.is_live());
+        assert_eq!(state.cursor_timestamp(), Some(19 * 60000));
+    }
+
+    #[test]
+    fn jump_to_oldest_within_one_page() {
+        let widget = CandleStickChart::new(Interval::OneMinute).candles(
+            (0..5)
+                .map(|i| Candle::new(i * 60000, 1.0, 2.0, 0.0, 1.5).unwrap())
+                .collect(),
+        );
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 40, 8, &mut state);
+
+        // every candle stays visible on the following frames
+        state.jump_to_oldest();
+        for _ in 0..2 {
+            render_with_state(widget.clone(), 40, 8, &mut state);
+            assert_eq!(state.cursor_timestamp(), Some(0));
+            assert_eq!(state.visible_end_timestamp(), Some(4 * 60000));
+        }
+    }
+
+    #[test]
+    fn logarithmic_candles() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 1.0, 1.5, 1.0, 1.5).unwrap(),
+                Candle::new(60000, 1.5, 10.0, 1.5, 10.0).unwrap(),
+                Candle::new(120000, 10.0, 100.0, 10.0, 100.0).unwrap(),
+            ])
+            .price_scale(PriceScale::Logarithmic);
+        // each candle rises tenfold and takes the same height
+        let buffer = render(widget, 19, 11);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "           │ xxx  ┃",
+                "           │ xxx  ┃",
+                "           │ xxx  ┃",
+                "    10.000 ├ xxx ╻┃",
+                "           │ xxx ┃ ",
+                "           │ xxx ┃ ",
+                "           │ xxx ┃ ",
+                "     1.000 ├ xxx┃╹ ",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn percentage_candles() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.0).unwrap(),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            ])
+            .price_scale(PriceScale::Percentage(None));
+        // relative to the close of the first candle
+        let buffer = render(widget, 20, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "   100.000% ├ xxx ╽┃",
+                "            │ xxx│┃┃",
+                "     0.000% ├ xxx│╹╿",
+                "            │ xxx│  ",
+                "  -100.000% ├ xxx│  ",
+                "xxxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn right_y_axis() {
+        let candles = vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ];
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles.clone())
+            .y_axis_position(YAxisPosition::Right);
+        let buffer = render(widget, 19, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "xxx ╽┃ ┤ 4.000     ",
+                "xxx│┃┃ │           ",
+                "xxx│╹╿ ┤ 2.000     ",
+                "xxx│   │           ",
+                "xxx│   ┤ 0.000     ",
+                "─────┴─┘xxxxxxxxxxx",
+                "*00:02xxxxxxxxxxxxx",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .y_axis_position(YAxisPosition::Both);
+        let buffer = render(widget, 30, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ x ╽┃ ┤ 4.000     ",
+                "           │ x│┃┃ │           ",
+                "     2.000 ├ x│╹╿ ┤ 2.000     ",
+                "           │ x│   │           ",
+                "     0.000 ├ x│   ┤ 0.000     ",
+                "xxxxxxxxxxx└──────┘xxxxxxxxxxx",
+                "xxxxxxxxxxxxx    xxxxxxxxxxxxx",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn last_price() {
+        let candles = vec![
+            Candle::new(0, 0.9, 1.5, 0.0, 1.2).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ];
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .show_last_price(true);
+        let buffer = render(widget.clone(), 19, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ╽┃",
+                "           │ xxx ┃┃",
+                "     2.300 ├ xxx┄╹╿",
+                "           │ xxx╽  ",
+                "     0.000 ├ xxx│  ",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let area = Rect::new(0, 0, 19, 8);
+        let mut buffer = Buffer::empty(area);
+        widget
+            .clone()
+            .render(area, &mut buffer, &mut CandleStickChartState::default());
+        assert_eq!(buffer[(5, 2)].bg, Color::Rgb(234, 74, 90));
+        assert_eq!(buffer[(15, 2)].fg, Color::Rgb(234, 74, 90));
+
+        // hidden once the cursor leaves the latest candle
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 19, 8, &mut state);
+        state.try_move_backward();
+        let buffer = render_with_state(widget, 19, 8, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxx ╽",
+                "           │ xxxx ┃",
+                "     2.000 ├ xxxx ╹",
+                "           │ xxxx╽┊",
+                "     0.000 ├ xxxx│┊",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx 00:01",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn price_levels() {
+        let candles = vec![
+            Candle::new(0, 0.9, 1.5, 0.0, 1.2).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+        ];
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .price_line(PriceLine::new(2.5).label("SL").symbol('-'))
+            .price_line(PriceLine::new(10.))
+            .price_band(
+                PriceBand::new(1.6, 0.9)
+                    .style(Style::default().bg(Color::Blue))
+                    .label("TP"),
+            );
+        let buffer = render(widget.clone(), 19, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ╽┃",
+                "           │ xxx ┃┃",
+                "        SL ├ xxx-╹╿",
+                "        TP │ xxx╽  ",
+                "     0.000 ├ xxx│  ",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let area = Rect::new(0, 0, 19, 8);
+        let mut buffer = Buffer::empty(area);
+        widget.render(area, &mut buffer, &mut CandleStickChartState::default());
+        assert_eq!(buffer[(16, 3)].bg, Color::Blue);
+        assert_eq!(buffer[(16, 2)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn markers() {
+        let candles = vec![
+            Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+            Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+            Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            Candle::new(180000, 2.3, 2.8, 1.9, 2.5).unwrap(),
+        ];
+        let markers = vec![
+            Marker::buy(0, 1.0).text("B"),
+            Marker::buy(0, 0.5),
+            Marker::sell(60000, 4.0),
+            Marker::new(180000, 2.5, MarkerPosition::AtPrice).symbol('x'),
+        ];
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .markers(markers);
+        let buffer = render(widget.clone(), 22, 12);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     5.000 ├ xxxxx ▼  ",
+                "           │ xxxxx ╷╻ ",
+                "           │ xxxxx╷┃┃ ",
+                "           │ xxxxx│┃╿x",
+                "           │ xxxxx┃ ╵╵",
+                "           │ xxxxx│   ",
+                "     0.000 ├ xxxxx╵   ",
+                "           │ xxxxx▲B  ",
+                "           │ xxxxx▲   ",
+                "xxxxxxxxxxx└─────────┴",
+                "xxxxxxxxxxxxx   *00:03",
+                "xxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        // merged columns stack their markers
+        let mut state = CandleStickChartState::default();
+        state.zoom_out();
+        state.zoom_out();
+        let widget = widget.fit_mode(ChartFitMode::Fit);
+        let buffer = render_with_state(widget, 16, 12, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     5.000 ├ ▼ x",
+                "           │ ╷╻x",
+                "           │ ┃┃x",
+                "           │ ┃xx",
+                "           │ ┃╵x",
+                "           │ │ x",
+                "     0.000 ├ ╵ x",
+                "           │ ▲Bx",
+                "           │ ▲ x",
+                "xxxxxxxxxxx└────",
+                "xxxxxxxxxxxxx   ",
+                "xxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn adjacent_markers() {
+        let candles = (0..5)
+            .map(|i| Candle::new(i * 60000, 1.0, 2.0, 0.5, 1.5).unwrap())
+            .collect::<Vec<_>>();
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .markers(vec![
+                Marker::buy(0, 1.0).text("BUY"),
+                Marker::buy(60000, 1.0),
+            ]);
+        // the second marker moves below the text of the first one
+        let buffer = render(widget, 21, 10);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     2.000 ├ xxx╷╷╷╷╷",
+                "           │ xxx│││││",
+                "           │ xxx┃┃┃┃┃",
+                "           │ xxx│││││",
+                "           │ xxx╵╵╵╵╵",
+                "     0.000 ├ xxx▲BUY ",
+                "           │ xxx ▲   ",
+                "xxxxxxxxxxx└────────┴",
+                "xxxxxxxxxxxxx  *00:04",
+                "xxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn drawings() {
+        let candles = (0..6)
+            .map(|i| {
+                let open = 1. + i as f64 * 0.5;
+                Candle::new(i * 60000, open, open + 0.6, open - 0.1, open + 0.5).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles.clone())
+            .drawing(Drawing::trend_line((-60000 * 100, -50.), (60000 * 5, 3.5)))
+            .drawing(Drawing::ray((0, 3.), (60000, 3.2)));
+        let buffer = render(widget.clone(), 20, 10);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ x   ⢀⠔⠁",
+                "           │ x ⡠⠊⠁⢠⠃",
+                "           │ x⠊  ⢠⠃ ",
+                "           │ x  ⢀⠎  ",
+                "     2.000 ├ x ⢀⠎   ",
+                "           │ x┃⡜    ",
+                "           │ x⡜     ",
+                "xxxxxxxxxxx└───────┴",
+                "xxxxxxxxxxxxx *00:05",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        // stays on the same candles when scrolled
+        let mut state = CandleStickChartState::default();
+        render_with_state(widget.clone(), 20, 10, &mut state);
+        state.try_move_backward();
+        state.try_move_backward();
+        let buffer = render_with_state(widget, 20, 10, &mut state);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     3.000 ├ xxx⠁  ╽",
+                "           │ xxx  ╷⡜",
+                "           │ xxx  ⢰⠁",
+                "     2.000 ├ xxx ╽⡎┊",
+                "           │ xxx╷⡸ ┊",
+                "           │ xxx⢀⠇ ┊",
+                "     1.000 ├ xxx⡜  ┊",
+                "xxxxxxxxxxx└───────┴",
+                "xxxxxxxxxxxxx  00:03",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .drawing(Drawing::fibonacci((60000, 1.), (60000 * 4, 4.)));
+        let buffer = render(widget, 30, 14);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxxxxxxxx 0⠉⠉⠉╽",
+                "           │ xxxxxxxxxxx    ╷┃",
+                "           │ xxxxxxxxxxx ⠤⠤⠤╽╵",
+                "           │ xxxxxxxxxxx   ╽╿ ",
+                "           │ xxxxxxxxxxx ⠉⠉┃╵⠉",
+                "           │ xxxxxxxxxxx ⠒╽╿⠒⠒",
+                "           │ xxxxxxxxxxx ╷┃⠤⠤⠤",
+                "     2.000 ├ xxxxxxxxxxx ╽╿   ",
+                "           │ xxxxxxxxxxx╷╿⠒⠒⠒⠒",
+                "           │ xxxxxxxxxxx┃╵    ",
+                "           │ xxxxxxxxxxx╿1⠤⠤⠤⠤",
+                "xxxxxxxxxxx└─────────────────┴",
+                "xxxxxxxxxxxxx           *00:05",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn ohlc_bars() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.9, 3.0, 0.0, 2.1).unwrap(),
+                Candle::new(60000, 2.1, 4.2, 2.1, 3.9).unwrap(),
+                Candle::new(120000, 3.9, 4.1, 2.0, 2.3).unwrap(),
+            ])
+            .chart_style(ChartStyle::OhlcBars);
+        let buffer = render(widget.clone(), 19, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ├┤",
+                "           │ xxx│││",
+                "     2.000 ├ xxx├┤├",
+                "           │ xxx┤  ",
+                "     0.000 ├ xxx│  ",
+                "xxxxxxxxxxx└──────┴",
+                "xxxxxxxxxxxxx*00:02",
+                "xxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let buffer = render(widget.fit_mode(ChartFitMode::Fit), 26, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├      ├─╴x╶┤  ",
+                "           │  │   │  x │  ",
+                "     2.000 ├  ├─╴╶┤  x ├─╴",
+                "           │ ╶┤      x    ",
+                "     0.000 ├  │      x    ",
+                "xxxxxxxxxxx└─────────────┴",
+                "xxxxxxxxxxxxx       *00:02",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn hollow_candles() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.5, 3.0, 0.0, 2.8).unwrap(),
+                Candle::new(60000, 3.9, 4.2, 2.1, 2.3).unwrap(),
+                Candle::new(120000, 4.0, 4.1, 2.0, 3.0).unwrap(),
+                Candle::new(180000, 1.0, 4.1, 0.5, 3.7).unwrap(),
+            ])
+            .chart_style(ChartStyle::HollowCandles);
+        let buffer = render(widget.clone(), 20, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxx ╽┃╥",
+                "           │ xxx║┃│║",
+                "     2.000 ├ xxx║╹│║",
+                "           │ xxx║  ╨",
+                "     0.000 ├ xxx╨  ╵",
+                "xxxxxxxxxxx└───────┴",
+                "xxxxxxxxxxxxx *00:03",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        // falling from its open but closing above the previous close
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buffer = Buffer::empty(area);
+        widget
+            .clone()
+            .render(area, &mut buffer, &mut CandleStickChartState::default());
+        assert_eq!(buffer[(18, 0)].fg, Color::Rgb(52, 208, 88));
+
+        let buffer = render(widget.fit_mode(ChartFitMode::Fit), 27, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├    x▐█▌▐█▌x┌─┐",
+                "           │ ┌─┐x▐█▌ │ x│ │",
+                "     2.000 ├ │ │x▐█▌ │ x│ │",
+                "           │ │ │x      x└┬┘",
+                "     0.000 ├ └─┘x      x │ ",
+                "xxxxxxxxxxx└──────────────┴",
+                "xxxxxxxxxxxxx        *00:03",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CandleStickChart>();
+        assert_send_sync::<CandleStickChartState>();
+    }
+
+    #[test]
+    fn hollow_candle_colors() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![Candle::new(0, 1.0, 4.1, 0.5, 3.7).unwrap()])
+            .chart_style(ChartStyle::HollowCandles)
+            .fit_mode(ChartFitMode::Fit)
+            .max_width(3)
+            .bullish_color(Color::Green)
+            .bullish_wick_color(Color::Yellow);
+        let area = Rect::new(0, 0, 16, 12);
+        let mut buffer = Buffer::empty(area);
+        widget.render(area, &mut buffer, &mut CandleStickChartState::default());
+        // the whole outline in the body color, only the wicks in the wick color
+        let colors = (0..9)
+            .map(|y| {
+                (13..16)
+                    .map(|x| match buffer[(x, y)].fg {
+                        _ if buffer[(x, y)].symbol() == " " => ' ',
+                        Color::Green => 'b',
+                        Color::Yellow => 'w',
+                        _ => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let symbols = (0..9)
+            .map(|y| {
+                (13..16)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            symbols,
+            vec![
+                " │ ",
+                "┌┴┐",
+                "│ │",
+                "│ │",
+                "│ │",
+                "│ │",
+                "└┬┘",
+                " │ ",
+                " │ "
+            ]
+        );
+        assert_eq!(
+            colors,
+            vec![
+                " w ", "bbb", "b b", "b b", "b b", "b b", "bbb", " w ", " w "
+            ]
+        );
+    }
+
+    #[test]
+    fn line_and_area() {
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(vec![
+                Candle::new(0, 0.5, 3.0, 0.0, 2.8).unwrap(),
+                Candle::new(60000, 3.9, 4.2, 2.1, 2.3).unwrap(),
+                Candle::new(120000, 4.0, 4.1, 2.0, 3.0).unwrap(),
+                Candle::new(180000, 1.0, 4.1, 0.5, 3.7).unwrap(),
+            ])
+            .chart_style(ChartStyle::Line);
+        let buffer = render(widget.clone(), 20, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxx⢀⠄",
+                "           │ xxx⢄⢀⠎x",
+                "     2.000 ├ xxx⠈⠊xx",
+                "           │ xxxxxxx",
+                "     0.000 ├ xxxxxxx",
+                "xxxxxxxxxxx└───────┴",
+                "xxxxxxxxxxxxx *00:03",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let buffer = render(widget.clone().fit_mode(ChartFitMode::Fit), 27, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxxxxxxx⣀⡠⠄x",
+                "           │ x⠤⣀xxx⢀⠤⠒⠉xxxx",
+                "     2.000 ├ xxx⠉⠑⠊⠁xxxxxxx",
+                "           │ xxxxxxxxxxxxxx",
+                "     0.000 ├ xxxxxxxxxxxxxx",
+                "xxxxxxxxxxx└──────────────┴",
+                "xxxxxxxxxxxxx        *00:03",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let widget = widget.chart_style(ChartStyle::Area);
+        let buffer = render(widget.clone(), 20, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxxx▃",
+                "           │ xxx▃x▅█",
+                "     2.000 ├ xxx█▆██",
+                "           │ xxx████",
+                "     0.000 ├ xxx████",
+                "xxxxxxxxxxx└───────┴",
+                "xxxxxxxxxxxxx *00:03",
+                "xxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+
+        let buffer = render(widget.fit_mode(ChartFitMode::Fit), 27, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "     4.000 ├ xxxxxxxxxxx▃▃▃",
+                "           │ ▃▃▃▃xxx▅▅▅▅███",
+                "     2.000 ├ ████▆▆▆███████",
+                "           │ ██████████████",
+                "     0.000 ├ ██████████████",
+                "xxxxxxxxxxx└──────────────┴",
+                "xxxxxxxxxxxxx        *00:03",
+                "xxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
+
+    #[test]
+    fn custom_numeric() {
+        let candles = vec![
+            Candle::new(0, 0.00001209, 0.00001230, 0.00001200, 0.00001221).unwrap(),
+            Candle::new(60000, 0.00001221, 0.00001242, 0.00001221, 0.00001239).unwrap(),
+            Candle::new(120000, 0.00001239, 0.00001241, 0.00001220, 0.00001223).unwrap(),
+        ];
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles.clone())
+            .y_axis_numeric(Numeric::new(6, 2));
+        let buffer = render(widget, 14, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "   0.00 ├ x ╽┃",
+                "        │ x│┃┃",
+                "   0.00 ├ x│╹╿",
+                "        │ x│  ",
+                "   0.00 ├ x│  ",
+                "xxxxxxxx└─────",
+                "xxxxxxxxxx    ",
+                "xxxxxxxxxxxxxx",
+            ])
+        );
+
+        let widget = CandleStickChart::new(Interval::OneMinute)
+            .candles(candles)
+            .y_axis_numeric(Numeric::auto());
+        let buffer = render(widget, 22, 8);
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "xx 0.0000124 ├ xxxx ╽┃",
+                "xx           │ xxxx│┃┃",
+                "xx 0.0000122 ├ xxxx│╹╿",
+                "xx           │ xxxx│  ",
+                "xx 0.0000120 ├ xxxx│  ",
+                "xxxxxxxxxxxxx└───────┴",
+                "xxxxxxxxxxxxxxx *00:02",
+                "xxxxxxxxxxxxxxxxxxxxxx",
+            ])
+        );
+    }
 }
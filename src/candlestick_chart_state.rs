@@ -1,31 +1,91 @@
 use std::cmp::{max, min};
 
-use crate::Interval;
+use ratatui::layout::{Position, Rect};
+
+use crate::{
+    Float,
+    candlestick_chart::Column,
+    mouse::{MouseEvent, MouseEventKind},
+};
+
+/// Deepest zoom in, where a candle is `MAX_ZOOM_IN + 1` cells wide
+const MAX_ZOOM_IN: i8 = 6;
+/// Deepest zoom out, where `2^MAX_ZOOM_OUT` candles are merged into one column
+const MAX_ZOOM_OUT: i8 = 6;
+
+/// Horizontal scale of a zoom level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Scale {
+    /// Number of intervals merged into one column
+    pub(crate) merge: i64,
+    /// Width of a column in cells
+    pub(crate) column_width: u16,
+}
+
+impl Scale {
+    fn new(zoom: i8) -> Self {
+        if zoom >= 0 {
+            Self {
+                merge: 1,
+                column_width: zoom as u16 + 1,
+            }
+        } else {
+            Self {
+                merge: 1 << -zoom,
+                column_width: 1,
+            }
+        }
+    }
+
+    /// Number of columns fitting in `width` cells
+    pub(crate) fn columns(&self, width: u16) -> u16 {
+        max(1, width / self.column_width)
+    }
+
+    /// Columns no wider than `width`, so a single candle still fits the plot
+    pub(crate) fn fit(self, width: u16) -> Self {
+        Self {
+            column_width: self.column_width.clamp(1, max(1, width)),
+            ..self
+        }
+    }
+
+    /// Blank cells left of the first column, so the latest column touches the right edge
+    pub(crate) fn offset(&self, width: u16) -> u16 {
+        width.saturating_sub(self.columns(width) * self.column_width)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct CandleStikcChartInfo {
-    cursor_first_timestamp: i64,
-    cursor_last_timestamp: i64,
-    interval: Interval,
-    latest_timestamp: i64,
-    need_previous_candles: bool,
+    pub(crate) cursor_first_timestamp: i64,
+    pub(crate) cursor_last_timestamp: i64,
+    /// Milliseconds between two columns
+    pub(crate) step: i64,
+    pub(crate) latest_timestamp: i64,
+    pub(crate) need_previous_candles: bool,
+    /// Timestamp shown in the last column
+    pub(crate) anchor_timestamp: i64,
+    /// First and last timestamp of the rendered window
+    pub(crate) visible_range: (i64, i64),
+    /// Lowest low and highest high of the rendered candles
+    pub(crate) price_range: Option<(Float, Float)>,
+    /// Candles and panes, relative to the rendered area
+    pub(crate) plot_area: Rect,
+    pub(crate) column_width: u16,
+    pub(crate) columns: Vec<Column>,
+    /// Timestamps of all candles, for snapping jumps
+    pub(crate) timestamps: Vec<i64>,
 }
 
 impl CandleStikcChartInfo {
-    pub(crate) fn new(
-        cursor_first_timestamp: i64,
-        cursor_last_timestamp: i64,
-        interval: Interval,
-        latest_timestamp: i64,
-        need_previous_candles: bool,
-    ) -> Self {
-        Self {
-            cursor_first_timestamp,
-            cursor_last_timestamp,
-            latest_timestamp,
-            interval,
-            need_previous_candles,
-        }
+    /// Milliseconds covered by the rendered window
+    fn page_width(&self) -> i64 {
+        self.anchor_timestamp - self.visible_range.0 + self.step
+    }
+
+    fn clamp(&self, timestamp: i64) -> i64 {
+        timestamp.clamp(self.cursor_first_timestamp, self.cursor_last_timestamp)
     }
 }
 
@@ -33,43 +93,120 @@ impl CandleStikcChartInfo {
 pub struct CandleStickChartState {
     pub(crate) info: Option<CandleStikcChartInfo>,
     pub(crate) cursor_timestamp: Option<i64>,
+    /// Timestamp of the last column after panning, otherwise the view follows the cursor
+    pub(crate) anchor_timestamp: Option<i64>,
+    /// Pointer column and anchor when the drag started, and whether it has panned since
+    pub(crate) drag: Option<(u16, i64, bool)>,
+    /// Positive levels stretch candles, negative levels merge them
+    pub(crate) zoom: i8,
 }
 
 impl CandleStickChartState {
     pub(crate) fn set_info(&mut self, info: CandleStikcChartInfo) {
-        if let Some(cursor_timestamp) = self.cursor_timestamp {
-            if cursor_timestamp == info.latest_timestamp {
-                self.cursor_timestamp = None;
-            } else {
-                self.cursor_timestamp = Some(
-                    cursor_timestamp.clamp(info.cursor_first_timestamp, info.cursor_last_timestamp),
-                );
+        // a cursor on the latest candle is live, a panned view stays where it is
+        self.cursor_timestamp = match self.cursor_timestamp {
+            Some(timestamp) if timestamp == info.latest_timestamp => None,
+            timestamp => timestamp.map(|timestamp| info.clamp(timestamp)),
+        };
+        self.anchor_timestamp = self.anchor_timestamp.map(|timestamp| info.clamp(timestamp));
+        self.info = Some(info);
+    }
+
+    /// Drop the panned view once the cursor leaves it, so the view follows the cursor again
+    fn follow_cursor(&mut self) {
+        if let (Some(info), Some(cursor_timestamp)) = (&self.info, self.cursor_timestamp) {
+            let (start, end) = info.visible_range;
+            if !(start..=end).contains(&cursor_timestamp) {
+                self.anchor_timestamp = None;
             }
         }
-        self.info = Some(info);
     }
 
     pub fn try_move_backward(&mut self) {
         if let Some(info) = &self.info {
             let cursor = if let Some(cursor_timestamp) = self.cursor_timestamp {
-                cursor_timestamp - info.interval as i64 * 1000
+                cursor_timestamp - info.step
             } else {
-                info.latest_timestamp - info.interval as i64 * 1000
+                info.latest_timestamp - info.step
             };
 
             self.cursor_timestamp = Some(max(cursor, info.cursor_first_timestamp));
+            self.follow_cursor();
         }
     }
 
     pub fn try_move_forward(&mut self) {
         if let Some(info) = &self.info {
             let cursor = if let Some(cursor_timestamp) = self.cursor_timestamp {
-                cursor_timestamp + info.interval as i64 * 1000
+                cursor_timestamp + info.step
             } else {
-                info.latest_timestamp + info.interval as i64 * 1000
+                info.latest_timestamp + info.step
             };
 
             self.cursor_timestamp = Some(min(cursor, info.cursor_last_timestamp));
+            self.follow_cursor();
+        }
+    }
+
+    /// Move the cursor and the view back by one visible width
+    pub fn page_backward(&mut self) {
+        self.page(-1);
+    }
+
+    /// Move the cursor and the view forward by one visible width
+    pub fn page_forward(&mut self) {
+        self.page(1);
+    }
+
+    fn page(&mut self, direction: i64) {
+        if let Some(info) = &self.info {
+            let distance = direction * info.page_width();
+            let cursor = self.cursor_timestamp.unwrap_or(info.latest_timestamp);
+
+            self.cursor_timestamp = Some(info.clamp(cursor + distance));
+            self.anchor_timestamp = self
+                .anchor_timestamp
+                .map(|anchor_timestamp| info.clamp(anchor_timestamp + distance));
+        }
+    }
+
+    /// Select the oldest loaded candle and show it at the left edge
+    pub fn jump_to_oldest(&mut self) {
+        if let Some(info) = &self.info {
+            let first_timestamp = info.cursor_first_timestamp;
+            self.cursor_timestamp = Some(first_timestamp);
+            self.anchor_timestamp = Some(min(
+                first_timestamp + info.page_width() - info.step,
+                info.latest_timestamp,
+            ));
+        }
+    }
+
+    /// Follow the latest candle again, same as [`reset_cursor`](Self::reset_cursor)
+    pub fn jump_to_latest(&mut self) {
+        self.reset_cursor();
+    }
+
+    /// Select the candle nearest to `timestamp`, clamped to the loaded candles
+    pub fn jump_to(&mut self, timestamp: i64) {
+        let Some(info) = &self.info else {
+            return;
+        };
+        let timestamps = &info.timestamps;
+        let (Some(first), Some(last)) = (timestamps.first(), timestamps.last()) else {
+            return;
+        };
+        let timestamp = timestamp.clamp(*first, *last);
+        let index = timestamps.partition_point(|t| *t < timestamp);
+        let nearest = [index.checked_sub(1), Some(index)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| timestamps.get(i).copied())
+            .min_by_key(|t| (t - timestamp).abs());
+
+        if let Some(nearest) = nearest {
+            self.cursor_timestamp = Some(nearest);
+            self.follow_cursor();
         }
     }
 
@@ -83,5 +220,125 @@ impl CandleStickChartState {
 
     pub fn reset_cursor(&mut self) {
         self.cursor_timestamp = None;
+        self.anchor_timestamp = None;
+    }
+
+    /// Select, pan and zoom with the mouse. `area` is the area the chart was last rendered to.
+    ///
+    /// Pressing selects the candle under the pointer and starts a drag, dragging pans the view
+    /// by whole columns and scrolling zooms in or out. A drag ending with the latest candle in
+    /// the last column follows the latest candle again.
+    pub fn handle_mouse_event(&mut self, event: MouseEvent, area: Rect) {
+        let Some(info) = &self.info else {
+            return;
+        };
+        let plot_area = Rect {
+            x: area.x + info.plot_area.x,
+            y: area.y + info.plot_area.y,
+            ..info.plot_area
+        };
+        let is_inside = plot_area.contains(Position::new(event.column, event.row));
+
+        match event.kind {
+            MouseEventKind::Down if is_inside => {
+                let x = event.column - plot_area.x;
+                if let Some(column) = info
+                    .columns
+                    .iter()
+                    .find(|c| (c.x..c.x + c.width).contains(&x))
+                {
+                    self.cursor_timestamp = Some(column.candle.timestamp);
+                    // keep the view where it is instead of moving the cursor to the right edge
+                    self.anchor_timestamp = Some(info.anchor_timestamp);
+                }
+                self.drag = Some((event.column, info.anchor_timestamp, false));
+            }
+            MouseEventKind::Drag => {
+                if let Some((column, anchor_timestamp, panned)) = &mut self.drag {
+                    let columns = (event.column as i64 - *column as i64) / info.column_width as i64;
+                    *panned |= columns != 0;
+                    self.anchor_timestamp =
+                        Some(info.clamp(*anchor_timestamp - columns * info.step));
+                }
+            }
+            MouseEventKind::Up => {
+                // panning back to the latest candle follows it again
+                let is_latest = self.anchor_timestamp.is_some_and(|anchor_timestamp| {
+                    (info.latest_timestamp - info.step + 1..=info.latest_timestamp)
+                        .contains(&anchor_timestamp)
+                });
+                if let Some((_, _, true)) = self.drag.take()
+                    && is_latest
+                {
+                    self.reset_cursor();
+                }
+            }
+            MouseEventKind::ScrollUp if is_inside => self.zoom_in(),
+            MouseEventKind::ScrollDown if is_inside => self.zoom_out(),
+            _ => {}
+        }
+    }
+
+    /// Stretch candles over one more cell, until a single candle fills the rendered plot
+    pub fn zoom_in(&mut self) {
+        if let Some(info) = &self.info
+            && info.column_width >= info.plot_area.width
+        {
+            return;
+        }
+        self.zoom = min(self.zoom + 1, MAX_ZOOM_IN);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = max(self.zoom - 1, -MAX_ZOOM_OUT);
+    }
+
+    pub fn reset_zoom(&mut self) {
+        self.zoom = 0;
+    }
+
+    pub fn zoom_level(&self) -> i8 {
+        self.zoom
+    }
+
+    pub(crate) fn scale(&self) -> Scale {
+        Scale::new(self.zoom)
+    }
+
+    /// Timestamp of the selected candle, which is the latest one while live.
+    /// `None` until the chart has been rendered, or when no candle is under the cursor, e.g. in
+    /// a gap of the data or past the latest candle.
+    pub fn cursor_timestamp(&self) -> Option<i64> {
+        let info = self.info.as_ref()?;
+        let timestamp = self.cursor_timestamp.unwrap_or(info.latest_timestamp);
+        let index = Column::find(&info.columns, timestamp)?;
+        Some(info.columns[index].candle.timestamp)
+    }
+
+    /// Timestamp of the first interval of the rendered window
+    pub fn visible_start_timestamp(&self) -> Option<i64> {
+        self.info.as_ref().map(|info| info.visible_range.0)
+    }
+
+    /// Timestamp of the last interval of the rendered window
+    pub fn visible_end_timestamp(&self) -> Option<i64> {
+        self.info.as_ref().map(|info| info.visible_range.1)
+    }
+
+    /// Lowest low of the rendered candles
+    pub fn visible_min_price(&self) -> Option<f64> {
+        let (min, _) = self.info.as_ref()?.price_range?;
+        Some(*min)
+    }
+
+    /// Highest high of the rendered candles
+    pub fn visible_max_price(&self) -> Option<f64> {
+        let (_, max) = self.info.as_ref()?.price_range?;
+        Some(*max)
+    }
+
+    /// Whether the view follows the latest candle
+    pub fn is_live(&self) -> bool {
+        self.cursor_timestamp.is_none() && self.anchor_timestamp.is_none()
     }
 }
@@ -0,0 +1,221 @@
+use ordered_float::OrderedFloat;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Style},
+};
+
+use crate::{
+    Float, braille::BrailleCanvas, candlestick_chart::Column, symbols::UNICODE_VOID, y_axis::YAxis,
+};
+
+/// Retracement ratios of a Fibonacci drawing
+const FIBONACCI_RATIOS: [f64; 7] = [0., 0.236, 0.382, 0.5, 0.618, 0.786, 1.];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DrawingKind {
+    TrendLine((i64, Float), (i64, Float)),
+    Ray((i64, Float), (i64, Float)),
+    Fibonacci((i64, Float), (i64, Float)),
+}
+
+/// A line drawn over the price area between `(timestamp, price)` points.
+///
+/// Points are chart coordinates, so drawings stay anchored to their candles while the view
+/// scrolls or zooms, and may lie outside the visible window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drawing {
+    kind: DrawingKind,
+    color: Color,
+}
+
+fn point((timestamp, price): (i64, f64)) -> (i64, Float) {
+    (timestamp, OrderedFloat::from(price))
+}
+
+impl Drawing {
+    /// Segment between two points
+    pub fn trend_line(from: (i64, f64), to: (i64, f64)) -> Self {
+        Self::new(DrawingKind::TrendLine(point(from), point(to)))
+    }
+
+    /// Line starting at `from` through `through`, continued to the edge of the price area
+    pub fn ray(from: (i64, f64), through: (i64, f64)) -> Self {
+        Self::new(DrawingKind::Ray(point(from), point(through)))
+    }
+
+    /// Retracement levels of the move from `from` to `to`. Level 0 is at `to` and level 1 at
+    /// `from`; the levels start at the earlier point and continue to the right edge.
+    pub fn fibonacci(from: (i64, f64), to: (i64, f64)) -> Self {
+        Self::new(DrawingKind::Fibonacci(point(from), point(to)))
+    }
+
+    fn new(kind: DrawingKind) -> Self {
+        Self {
+            kind,
+            color: Color::Cyan,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Prices and labels of the Fibonacci levels, empty for other drawings
+    fn levels(&self) -> Vec<(Float, String)> {
+        let DrawingKind::Fibonacci((_, from), (_, to)) = self.kind else {
+            return Vec::new();
+        };
+        FIBONACCI_RATIOS
+            .iter()
+            .map(|ratio| (to - (to - from) * ratio, ratio.to_string()))
+            .collect()
+    }
+
+    /// Draw trend lines and rays, see [`render_levels`](Self::render_levels) for Fibonacci levels
+    pub(crate) fn render(
+        &self,
+        columns: &[Column],
+        interval: i64,
+        y_axis: &YAxis,
+        canvas: &mut BrailleCanvas,
+    ) {
+        let Some(dot_x) = DotX::new(columns, interval) else {
+            return;
+        };
+        let dot =
+            |(timestamp, price): (i64, Float)| (dot_x.get(timestamp), *y_axis.calc_y(price) * 4.);
+
+        match self.kind {
+            DrawingKind::TrendLine(from, to) => canvas.clipped_line(dot(from), dot(to), self.color),
+            DrawingKind::Ray(from, through) => {
+                let (from, through) = (dot(from), dot(through));
+                let (dx, dy) = (through.0 - from.0, through.1 - from.1);
+                let length = dx.hypot(dy);
+                if length == 0. {
+                    return;
+                }
+                // far enough to leave the canvas in any direction
+                let reach =
+                    from.0.abs() + from.1.abs() + (canvas.dot_width() + canvas.dot_height()) as f64;
+                let scale = reach / length + 1.;
+                let end = (from.0 + dx * scale, from.1 + dy * scale);
+                canvas.clipped_line(from, end, self.color);
+            }
+            DrawingKind::Fibonacci(..) => {}
+        }
+    }
+
+    /// Draw the Fibonacci levels, meant for a canvas rendered behind the candles
+    pub(crate) fn render_levels(
+        &self,
+        columns: &[Column],
+        interval: i64,
+        y_axis: &YAxis,
+        canvas: &mut BrailleCanvas,
+    ) {
+        let DrawingKind::Fibonacci((from, _), (to, _)) = self.kind else {
+            return;
+        };
+        let Some(dot_x) = DotX::new(columns, interval) else {
+            return;
+        };
+        let start = dot_x.get(from.min(to));
+        for (price, _) in self.levels() {
+            let y = *y_axis.calc_y(price) * 4.;
+            canvas.clipped_line((start, y), (canvas.dot_width() as f64, y), self.color);
+        }
+    }
+
+    /// Write the Fibonacci ratios at the start of their levels, skipping labels that would cover
+    /// anything but empty or dotted cells of `area`
+    pub(crate) fn render_labels(
+        &self,
+        columns: &[Column],
+        interval: i64,
+        y_axis: &YAxis,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let DrawingKind::Fibonacci((from, _), (to, _)) = self.kind else {
+            return;
+        };
+        let Some(dot_x) = DotX::new(columns, interval) else {
+            return;
+        };
+        let x = (dot_x.get(from.min(to)) / 2.).max(0.) as u16;
+        for (price, label) in self.levels() {
+            let Some(row) = y_axis.visible_row(price) else {
+                continue;
+            };
+            let y = area.y + row;
+            let cells = (x..area.width)
+                .take(label.chars().count())
+                .map(|dx| area.x + dx);
+            // over the start of the level, but never over candles
+            let is_free = cells.clone().all(|cell_x| {
+                buf.cell((cell_x, y))
+                    .is_some_and(|cell| cell.symbol() == UNICODE_VOID || is_braille(cell.symbol()))
+            });
+            if !is_free {
+                continue;
+            }
+            for (cell_x, char) in cells.zip(label.chars()) {
+                if let Some(cell) = buf.cell_mut((cell_x, y)) {
+                    cell.set_char(char)
+                        .set_style(Style::default().fg(self.color));
+                }
+            }
+        }
+    }
+}
+
+fn is_braille(symbol: &str) -> bool {
+    symbol
+        .chars()
+        .next()
+        .is_some_and(|c| ('\u{2800}'..='\u{28ff}').contains(&c))
+}
+
+/// Horizontal dot position of timestamps, interpolated between the column centers and
+/// extrapolated beyond the first and the last column
+struct DotX<'a> {
+    columns: &'a [Column],
+    /// Dots per millisecond outside the columns
+    slope: f64,
+}
+
+impl<'a> DotX<'a> {
+    fn new(columns: &'a [Column], interval: i64) -> Option<Self> {
+        let (first, last) = (columns.first()?, columns.last()?);
+        let slope = if columns.len() == 1 {
+            let duration = first.end_timestamp - first.candle.timestamp + interval;
+            first.width as f64 * 2. / duration as f64
+        } else {
+            (Self::center(last) - Self::center(first))
+                / (last.candle.timestamp - first.candle.timestamp) as f64
+        };
+        Some(Self { columns, slope })
+    }
+
+    fn center(column: &Column) -> f64 {
+        (column.x * 2 + column.width - 1) as f64
+    }
+
+    fn get(&self, timestamp: i64) -> f64 {
+        let index = self
+            .columns
+            .partition_point(|c| c.candle.timestamp <= timestamp);
+        let anchor = match (index.checked_sub(1), self.columns.get(index)) {
+            (Some(before), Some(after)) => {
+                let (before, after) = (&self.columns[before], after);
+                let span = (after.candle.timestamp - before.candle.timestamp) as f64;
+                let ratio = (timestamp - before.candle.timestamp) as f64 / span;
+                return Self::center(before) + (Self::center(after) - Self::center(before)) * ratio;
+            }
+            (Some(before), None) => &self.columns[before],
+            (None, _) => &self.columns[0],
+        };
+        Self::center(anchor) + (timestamp - anchor.candle.timestamp) as f64 * self.slope
+    }
+}
@@ -0,0 +1,158 @@
+/// Turns an axis value into its label, see [`Numeric::formatter`](crate::Numeric::formatter).
+///
+/// Closures `Fn(f64, usize) -> String` are formatters too, e.g. basis points with
+/// `|value, _| format!("{:.0}bp", value * 10_000.)`.
+pub trait LabelFormatter: Send + Sync {
+    /// Label of `value`, where `decimals` is the scale picked by the [`Numeric`](crate::Numeric)
+    fn format(&self, value: f64, decimals: usize) -> String;
+}
+
+impl<F: Fn(f64, usize) -> String + Send + Sync> LabelFormatter for F {
+    fn format(&self, value: f64, decimals: usize) -> String {
+        self(value, decimals)
+    }
+}
+
+/// Thousands as `K`, millions as `M`, billions as `G` and trillions as `T`, e.g. `65.2K`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SiSuffix {
+    /// Decimals of suffixed values
+    decimals: usize,
+}
+
+impl Default for SiSuffix {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl SiSuffix {
+    pub fn new(decimals: usize) -> Self {
+        Self { decimals }
+    }
+}
+
+impl LabelFormatter for SiSuffix {
+    fn format(&self, value: f64, decimals: usize) -> String {
+        const SUFFIXES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "K")];
+
+        match SUFFIXES.iter().find(|(unit, _)| value.abs() >= *unit) {
+            Some((unit, suffix)) => format!("{:.*}{}", self.decimals, value / unit, suffix),
+            None => format!("{:.*}", decimals, value),
+        }
+    }
+}
+
+/// Integer digits grouped by three, e.g. `42,184.08`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grouped {
+    separator: char,
+}
+
+impl Default for Grouped {
+    fn default() -> Self {
+        Self::new(',')
+    }
+}
+
+impl Grouped {
+    pub fn new(separator: char) -> Self {
+        Self { separator }
+    }
+}
+
+impl LabelFormatter for Grouped {
+    fn format(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+
+        let mut result = String::new();
+        if value.is_sign_negative() && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            result.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i).is_multiple_of(3) {
+                result.push(self.separator);
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push('.');
+            result.push_str(fraction);
+        }
+        result
+    }
+}
+
+/// Grouped digits behind a currency symbol, e.g. `$42,184.08` or `-$12.50`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Currency {
+    symbol: String,
+    grouped: Grouped,
+}
+
+impl Currency {
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            grouped: Grouped::default(),
+        }
+    }
+
+    pub fn separator(mut self, separator: char) -> Self {
+        self.grouped = Grouped::new(separator);
+        self
+    }
+}
+
+impl LabelFormatter for Currency {
+    fn format(&self, value: f64, decimals: usize) -> String {
+        let grouped = self.grouped.format(value, decimals);
+        match grouped.strip_prefix('-') {
+            Some(grouped) => format!("-{}{}", self.symbol, grouped),
+            None => format!("{}{}", self.symbol, grouped),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Currency, Grouped, LabelFormatter, SiSuffix};
+
+    #[test]
+    fn si_suffix() {
+        let formatter = SiSuffix::default();
+        assert_eq!(formatter.format(65_200., 2), "65.2K");
+        assert_eq!(formatter.format(-1_234_567., 2), "-1.2M");
+        assert_eq!(formatter.format(999.5, 2), "999.50");
+    }
+
+    #[test]
+    fn grouped() {
+        let formatter = Grouped::default();
+        assert_eq!(formatter.format(42_184.08, 2), "42,184.08");
+        assert_eq!(formatter.format(-1_234_567., 0), "-1,234,567");
+        assert_eq!(formatter.format(123., 1), "123.0");
+        assert_eq!(formatter.format(-0.001, 2), "0.00");
+        assert_eq!(Grouped::new(' ').format(1_000., 0), "1 000");
+    }
+
+    #[test]
+    fn currency() {
+        assert_eq!(Currency::new("$").format(42_184.08, 2), "$42,184.08");
+        assert_eq!(Currency::new("$").format(-12.5, 2), "-$12.50");
+        assert_eq!(
+            Currency::new("€").separator('.').format(1_234., 0),
+            "€1.234"
+        );
+    }
+
+    #[test]
+    fn closure() {
+        let basis_points = |value: f64, _| format!("{:.0}bp", value * 10_000.);
+        assert_eq!(basis_points.format(0.0125, 0), "125bp");
+    }
+}
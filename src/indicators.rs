@@ -0,0 +1,452 @@
+//! Technical indicators computed from candles.
+//!
+//! Every indicator is a small state machine fed one candle at a time through
+//! [`Indicator::next`], so appending a candle to a live chart only costs one update. Use
+//! [`Indicator::compute`] to evaluate a whole slice at once. Results are keyed by candle
+//! timestamp and can be passed straight to [`Overlay::new`](crate::Overlay::new).
+//!
+//! Indicators are `Clone`: to evaluate a candle that is still forming, feed it to a copy and keep
+//! the original for the next closed candle.
+
+use std::collections::VecDeque;
+
+use ordered_float::OrderedFloat;
+
+use crate::Candle;
+
+pub trait Indicator {
+    type Output;
+
+    /// Feed the next candle. Returns `None` until enough candles have been seen.
+    fn next(&mut self, candle: &Candle) -> Option<Self::Output>;
+
+    /// Feed all candles in order and collect the values keyed by timestamp
+    fn compute(&mut self, candles: &[Candle]) -> Vec<(i64, Self::Output)> {
+        candles
+            .iter()
+            .filter_map(|candle| self.next(candle).map(|value| (candle.timestamp, value)))
+            .collect()
+    }
+}
+
+/// Extract one line of a multi-valued indicator, e.g. `select(&macd, |v| v.signal)`
+pub fn select<T>(values: &[(i64, T)], f: impl Fn(&T) -> f64) -> Vec<(i64, f64)> {
+    values
+        .iter()
+        .map(|(timestamp, value)| (*timestamp, f(value)))
+        .collect()
+}
+
+/// Simple moving average of closes
+#[derive(Debug, Clone)]
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+        self.sum += value;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap();
+        }
+
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+}
+
+impl Indicator for Sma {
+    type Output = f64;
+
+    fn next(&mut self, candle: &Candle) -> Option<f64> {
+        self.update(*candle.close)
+    }
+}
+
+/// Exponential moving average of closes, seeded with the SMA of the first `period` values
+#[derive(Debug, Clone)]
+pub struct Ema {
+    alpha: f64,
+    seed: Sma,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize) -> Self {
+        Self {
+            alpha: 2. / (period as f64 + 1.),
+            seed: Sma::new(period),
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(prev) => Some(prev + self.alpha * (value - prev)),
+            None => self.seed.update(value),
+        };
+        self.value
+    }
+}
+
+impl Indicator for Ema {
+    type Output = f64;
+
+    fn next(&mut self, candle: &Candle) -> Option<f64> {
+        self.update(*candle.close)
+    }
+}
+
+/// Wilder's smoothing: a running mean over the first `period` values, then
+/// `prev + (value - prev) / period`
+#[derive(Debug, Clone)]
+struct Wilder {
+    period: usize,
+    count: usize,
+    value: f64,
+}
+
+impl Wilder {
+    fn new(period: usize) -> Self {
+        assert!(period > 0);
+        Self {
+            period,
+            count: 0,
+            value: 0.,
+        }
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if self.count < self.period {
+            self.count += 1;
+            self.value += (value - self.value) / self.count as f64;
+        } else {
+            self.value += (value - self.value) / self.period as f64;
+        }
+
+        (self.count == self.period).then_some(self.value)
+    }
+}
+
+/// Relative strength index (0-100) with Wilder's smoothing
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    prev_close: Option<f64>,
+    gain: Wilder,
+    loss: Wilder,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Self {
+        Self {
+            prev_close: None,
+            gain: Wilder::new(period),
+            loss: Wilder::new(period),
+        }
+    }
+}
+
+impl Indicator for Rsi {
+    type Output = f64;
+
+    fn next(&mut self, candle: &Candle) -> Option<f64> {
+        let close = *candle.close;
+        let prev_close = self.prev_close.replace(close)?;
+        let change = close - prev_close;
+
+        let gain = self.gain.update(change.max(0.));
+        let loss = self.loss.update((-change).max(0.));
+        match (gain, loss) {
+            (Some(_), Some(0.)) => Some(100.),
+            (Some(gain), Some(loss)) => Some(100. - 100. / (1. + gain / loss)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdValue {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// Moving average convergence divergence
+#[derive(Debug, Clone)]
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+}
+
+impl Macd {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        Self {
+            fast: Ema::new(fast),
+            slow: Ema::new(slow),
+            signal: Ema::new(signal),
+        }
+    }
+}
+
+impl Default for Macd {
+    fn default() -> Self {
+        Self::new(12, 26, 9)
+    }
+}
+
+impl Indicator for Macd {
+    type Output = MacdValue;
+
+    fn next(&mut self, candle: &Candle) -> Option<MacdValue> {
+        let fast = self.fast.next(candle);
+        let slow = self.slow.next(candle);
+        let macd = fast? - slow?;
+        let signal = self.signal.update(macd)?;
+
+        Some(MacdValue {
+            macd,
+            signal,
+            histogram: macd - signal,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BollingerValue {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+/// SMA of closes with bands `multiplier` standard deviations away
+#[derive(Debug, Clone)]
+pub struct BollingerBands {
+    multiplier: f64,
+    sma: Sma,
+}
+
+impl BollingerBands {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            multiplier,
+            sma: Sma::new(period),
+        }
+    }
+}
+
+impl Default for BollingerBands {
+    fn default() -> Self {
+        Self::new(20, 2.)
+    }
+}
+
+impl Indicator for BollingerBands {
+    type Output = BollingerValue;
+
+    fn next(&mut self, candle: &Candle) -> Option<BollingerValue> {
+        let middle = self.sma.next(candle)?;
+        let window = &self.sma.window;
+        let variance =
+            window.iter().map(|v| (v - middle).powi(2)).sum::<f64>() / window.len() as f64;
+        let deviation = variance.sqrt() * self.multiplier;
+
+        Some(BollingerValue {
+            upper: middle + deviation,
+            middle,
+            lower: middle - deviation,
+        })
+    }
+}
+
+/// Average true range with Wilder's smoothing
+#[derive(Debug, Clone)]
+pub struct Atr {
+    prev_close: Option<f64>,
+    average: Wilder,
+}
+
+impl Atr {
+    pub fn new(period: usize) -> Self {
+        Self {
+            prev_close: None,
+            average: Wilder::new(period),
+        }
+    }
+}
+
+impl Indicator for Atr {
+    type Output = f64;
+
+    fn next(&mut self, candle: &Candle) -> Option<f64> {
+        let (high, low) = (*candle.high, *candle.low);
+        let true_range = match self.prev_close.replace(*candle.close) {
+            Some(prev_close) => (high - low)
+                .max((high - prev_close).abs())
+                .max((low - prev_close).abs()),
+            None => high - low,
+        };
+
+        self.average.update(true_range)
+    }
+}
+
+/// Heikin-Ashi candles: the close is the average of the open, high, low and close, the open is
+/// the middle of the previous Heikin-Ashi body, and the high and low cover both. Timestamps and
+/// volumes are kept.
+#[derive(Debug, Clone, Default)]
+pub struct HeikinAshi {
+    previous: Option<(f64, f64)>,
+}
+
+impl HeikinAshi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Indicator for HeikinAshi {
+    type Output = Candle;
+
+    fn next(&mut self, candle: &Candle) -> Option<Candle> {
+        let (open, high, low, close) = (*candle.open, *candle.high, *candle.low, *candle.close);
+        let ha_close = (open + high + low + close) / 4.;
+        let ha_open = match self.previous {
+            Some((previous_open, previous_close)) => (previous_open + previous_close) / 2.,
+            None => (open + close) / 2.,
+        };
+        self.previous = Some((ha_open, ha_close));
+
+        Some(Candle {
+            timestamp: candle.timestamp,
+            open: OrderedFloat::from(ha_open),
+            high: OrderedFloat::from(high.max(ha_open).max(ha_close)),
+            low: OrderedFloat::from(low.min(ha_open).min(ha_close)),
+            close: OrderedFloat::from(ha_close),
+            volume: candle.volume,
+        })
+    }
+}
+
+/// Convert candles to Heikin-Ashi candles. Use [`HeikinAshi`] to convert appended candles one at
+/// a time.
+pub fn heikin_ashi(candles: &[Candle]) -> Vec<Candle> {
+    let mut heikin_ashi = HeikinAshi::new();
+    candles
+        .iter()
+        .filter_map(|candle| heikin_ashi.next(candle))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Atr, BollingerBands, Ema, HeikinAshi, Indicator, Macd, Rsi, Sma, heikin_ashi};
+    use crate::Candle;
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle::new(i as i64 * 60000, c, c + 1., c - 1., c).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn sma() {
+        let values = Sma::new(3).compute(&candles(&[1., 2., 3., 4., 5.]));
+        assert_eq!(values, vec![(120000, 2.), (180000, 3.), (240000, 4.)]);
+    }
+
+    #[test]
+    fn ema() {
+        let values = Ema::new(3).compute(&candles(&[1., 2., 3., 4., 5.]));
+        assert_eq!(values, vec![(120000, 2.), (180000, 3.), (240000, 4.)]);
+
+        let values = Ema::new(3).compute(&candles(&[2., 2., 2., 6.]));
+        assert_eq!(values, vec![(120000, 2.), (180000, 4.)]);
+    }
+
+    #[test]
+    fn rsi() {
+        let values = Rsi::new(2).compute(&candles(&[1., 2., 3., 4.]));
+        assert_eq!(values, vec![(120000, 100.), (180000, 100.)]);
+
+        let values = Rsi::new(2).compute(&candles(&[1., 2., 1.]));
+        assert_eq!(values, vec![(120000, 50.)]);
+    }
+
+    #[test]
+    fn macd() {
+        let values = Macd::new(1, 2, 2).compute(&candles(&[1., 2., 3., 4.]));
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].0, 120000);
+        assert!((values[0].1.macd - 0.5).abs() < 1e-9);
+        assert!((values[0].1.signal - 0.5).abs() < 1e-9);
+        assert_eq!(values[0].1.histogram, values[0].1.macd - values[0].1.signal);
+    }
+
+    #[test]
+    fn bollinger_bands() {
+        let values = BollingerBands::new(2, 2.).compute(&candles(&[1., 3.]));
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].1.middle, 2.);
+        assert_eq!(values[0].1.upper, 4.);
+        assert_eq!(values[0].1.lower, 0.);
+    }
+
+    #[test]
+    fn atr() {
+        // ranges are 2, gaps push the true range of the last candle to 4
+        let values = Atr::new(2).compute(&candles(&[1., 1., 4.]));
+        assert_eq!(values, vec![(60000, 2.), (120000, 3.)]);
+    }
+
+    #[test]
+    fn heikin_ashi_candles() {
+        let candles = vec![
+            Candle::new(0, 10., 14., 8., 12.).unwrap(),
+            Candle::new(60000, 12., 13., 9., 10.)
+                .unwrap()
+                .with_volume(5.),
+        ];
+        let converted = heikin_ashi(&candles);
+        assert_eq!(converted[0], Candle::new(0, 11., 14., 8., 11.).unwrap());
+        // open from the middle of the previous body
+        assert_eq!(
+            converted[1],
+            Candle::new(60000, 11., 13., 9., 11.)
+                .unwrap()
+                .with_volume(5.)
+        );
+
+        let mut incremental = HeikinAshi::new();
+        incremental.compute(&candles[..1]);
+        assert_eq!(incremental.next(&candles[1]).as_ref(), converted.get(1));
+    }
+
+    #[test]
+    fn incremental() {
+        let candles = candles(&[3., 1., 4., 1., 5., 9., 2., 6.]);
+        let batch = Rsi::new(3).compute(&candles);
+
+        let mut rsi = Rsi::new(3);
+        rsi.compute(&candles[..5]);
+        let appended = candles[5..]
+            .iter()
+            .filter_map(|c| rsi.next(c).map(|v| (c.timestamp, v)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(appended, batch[batch.len() - 3..]);
+    }
+}
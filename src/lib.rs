@@ -1,15 +1,35 @@
 use ordered_float::OrderedFloat;
 
+mod braille;
 mod candle;
 mod candlestick_chart;
 mod candlestick_chart_state;
+mod drawing;
+pub mod formatter;
+pub mod indicators;
+mod marker;
+mod mouse;
+mod overlay;
+mod pane;
+mod price_level;
+mod price_only;
 mod symbols;
+mod tooltip;
 mod x_axis;
 mod y_axis;
 
 pub use candle::Candle;
-pub use candlestick_chart::{CandleStickChart, ChartFitMode};
+pub use candlestick_chart::{CandleStickChart, ChartFitMode, ChartStyle};
 pub use candlestick_chart_state::CandleStickChartState;
+pub use drawing::Drawing;
+pub use marker::{Marker, MarkerPosition};
+pub use mouse::{MouseEvent, MouseEventKind};
+pub use overlay::Overlay;
+pub use pane::Pane;
+pub use price_level::{PriceBand, PriceLine};
+pub use price_only::{BoxSize, Kagi, LineBreak, PointAndFigure, PriceOnlyChart, Renko};
+pub use tooltip::TooltipPosition;
 pub use x_axis::Interval;
+pub use y_axis::{Numeric, PriceScale, YAxisPosition};
 
 pub(crate) type Float = OrderedFloat<f64>;
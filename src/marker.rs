@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use ordered_float::OrderedFloat;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Style},
+};
+
+use crate::{Float, candlestick_chart::Column, symbols::UNICODE_VOID, y_axis::YAxis};
+
+/// Where a marker is placed relative to the column of its timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerPosition {
+    /// Under the low, e.g. a buy
+    BelowLow,
+    /// Over the high, e.g. a sell
+    AboveHigh,
+    /// At the price of the marker
+    AtPrice,
+}
+
+/// A glyph marking an event on a candle, e.g. a fill of an order.
+///
+/// Markers falling into the same merged column are stacked away from the candle instead of
+/// being drawn over each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    timestamp: i64,
+    price: Float,
+    position: MarkerPosition,
+    symbol: char,
+    color: Color,
+    text: Option<String>,
+}
+
+impl Marker {
+    /// `▲` below the low for `BelowLow`, `▼` above the high for `AboveHigh`, `●` otherwise
+    pub fn new(timestamp: i64, price: f64, position: MarkerPosition) -> Self {
+        let symbol = match position {
+            MarkerPosition::BelowLow => '▲',
+            MarkerPosition::AboveHigh => '▼',
+            MarkerPosition::AtPrice => '●',
+        };
+        Self {
+            timestamp,
+            price: OrderedFloat::from(price),
+            position,
+            symbol,
+            color: Color::Reset,
+            text: None,
+        }
+    }
+
+    /// Green `▲` below the low of the candle
+    pub fn buy(timestamp: i64, price: f64) -> Self {
+        Self::new(timestamp, price, MarkerPosition::BelowLow).color(Color::Rgb(52, 208, 88))
+    }
+
+    /// Red `▼` above the high of the candle
+    pub fn sell(timestamp: i64, price: f64) -> Self {
+        Self::new(timestamp, price, MarkerPosition::AboveHigh).color(Color::Rgb(234, 74, 90))
+    }
+
+    pub fn symbol(mut self, symbol: char) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Short text written right of the glyph where the cells are empty
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+}
+
+/// Markers of the visible columns with the index of their column
+fn visible<'a>(
+    markers: &'a [Marker],
+    columns: &'a [Column],
+) -> impl Iterator<Item = (usize, &'a Marker)> + 'a {
+    markers
+        .iter()
+        .filter_map(|marker| Column::find(columns, marker.timestamp).map(|index| (index, marker)))
+}
+
+/// Cell of the glyph of markers in `column`
+fn middle(column: &Column) -> u16 {
+    column.x + (column.width - 1) / 2
+}
+
+/// Most markers stacked over the high and under the low of a single column, counting the text
+/// of markers reaching into the column as well
+pub(crate) fn stack_heights(markers: &[Marker], columns: &[Column]) -> (u16, u16) {
+    let mut counts: HashMap<(usize, MarkerPosition), u16> = HashMap::new();
+    for (index, marker) in visible(markers, columns) {
+        *counts.entry((index, marker.position)).or_default() += 1;
+        let Some(text) = &marker.text else {
+            continue;
+        };
+        let x = middle(&columns[index]);
+        let cells = x + 1..=x + text.chars().count() as u16;
+        for (other, column) in columns.iter().enumerate() {
+            if other != index && cells.contains(&middle(column)) {
+                *counts.entry((other, marker.position)).or_default() += 1;
+            }
+        }
+    }
+    let most = |position| {
+        counts
+            .iter()
+            .filter(|((_, p), _)| *p == position)
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or_default()
+    };
+    (
+        most(MarkerPosition::AboveHigh),
+        most(MarkerPosition::BelowLow),
+    )
+}
+
+/// Draw the markers in the middle of their columns. A marker whose cell is taken by another
+/// marker or its text moves one row further from the candle, downwards under the low and
+/// upwards otherwise.
+pub(crate) fn render_markers(
+    markers: &[Marker],
+    columns: &[Column],
+    y_axis: &YAxis,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let mut taken: HashSet<(u16, i32)> = HashSet::new();
+    for (index, marker) in visible(markers, columns) {
+        let column = &columns[index];
+        let candle = &column.candle;
+        let (row, direction) = match marker.position {
+            MarkerPosition::BelowLow => (y_axis.visible_row(candle.low).map(|r| r as i32 + 1), 1),
+            MarkerPosition::AboveHigh => {
+                (y_axis.visible_row(candle.high).map(|r| r as i32 - 1), -1)
+            }
+            MarkerPosition::AtPrice => (y_axis.visible_row(marker.price).map(|r| r as i32), -1),
+        };
+        let Some(mut row) = row else {
+            continue;
+        };
+        let x = middle(column);
+        while taken.contains(&(x, row)) {
+            row += direction;
+        }
+        if !(0..area.height as i32).contains(&row) {
+            continue;
+        }
+        taken.insert((x, row));
+
+        let y = area.y + row as u16;
+        let style = Style::default().fg(marker.color);
+        if let Some(cell) = buf.cell_mut((area.x + x, y)) {
+            cell.set_char(marker.symbol).set_style(style);
+        }
+        let Some(text) = &marker.text else {
+            continue;
+        };
+        for (dx, char) in text.chars().enumerate() {
+            let cell_x = x + 1 + dx as u16;
+            if cell_x >= area.width || taken.contains(&(cell_x, row)) {
+                break;
+            }
+            match buf.cell_mut((area.x + cell_x, y)) {
+                Some(cell) if cell.symbol() == UNICODE_VOID => {
+                    cell.set_char(char).set_style(style);
+                    taken.insert((cell_x, row));
+                }
+                _ => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+/// A mouse event in terminal cells, independent of the terminal backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    pub column: u16,
+    pub row: u16,
+    pub kind: MouseEventKind,
+}
+
+impl MouseEvent {
+    pub fn new(column: u16, row: u16, kind: MouseEventKind) -> Self {
+        Self { column, row, kind }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseEventKind {
+    /// Left button pressed
+    Down,
+    /// Moved with the left button pressed
+    Drag,
+    /// Left button released
+    Up,
+    ScrollUp,
+    ScrollDown,
+}
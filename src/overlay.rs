@@ -0,0 +1,58 @@
+use ordered_float::OrderedFloat;
+use ratatui::style::Color;
+
+use crate::{Float, braille::BrailleCanvas, candlestick_chart::Column, y_axis::YAxis};
+
+/// A timestamp-keyed line drawn over the price area, e.g. a moving average or VWAP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+    values: Vec<(i64, Float)>,
+    color: Color,
+}
+
+impl Overlay {
+    /// Values are keyed by candle timestamp. Non-finite values are skipped.
+    pub fn new(values: Vec<(i64, f64)>, color: Color) -> Self {
+        let mut values = values
+            .into_iter()
+            .filter(|(_, value)| value.is_finite())
+            .map(|(timestamp, value)| (timestamp, OrderedFloat::from(value)))
+            .collect::<Vec<_>>();
+        values.sort_by_key(|(timestamp, _)| *timestamp);
+
+        Self { values, color }
+    }
+
+    pub(crate) fn visible_values(&self, start: i64, end: i64) -> impl Iterator<Item = Float> + '_ {
+        self.values
+            .iter()
+            .filter(move |(timestamp, _)| *timestamp >= start && *timestamp <= end)
+            .map(|(_, value)| *value)
+    }
+
+    /// Connect the value of each column with straight lines. Merged columns show the last value
+    /// falling into them, the same way a merged candle shows the last close.
+    pub(crate) fn render(&self, columns: &[Column], y_axis: &YAxis, canvas: &mut BrailleCanvas) {
+        let mut points: Vec<(usize, (i64, i64))> = Vec::new();
+        for (timestamp, value) in &self.values {
+            let Some(index) = Column::find(columns, *timestamp) else {
+                continue;
+            };
+            let column = &columns[index];
+            let x = column.x as i64 * 2 + column.width as i64 - 1;
+            let y = ((*y_axis.calc_y(*value) * 4.) as i64).min(canvas.dot_height() - 1);
+
+            match points.last_mut() {
+                Some((last_index, point)) if *last_index == index => *point = (x, y),
+                _ => points.push((index, (x, y))),
+            }
+        }
+
+        if let [(_, point)] = points.as_slice() {
+            canvas.set(point.0, point.1, self.color);
+        }
+        for window in points.windows(2) {
+            canvas.line(window[0].1, window[1].1, self.color);
+        }
+    }
+}
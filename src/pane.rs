@@ -0,0 +1,197 @@
+use ordered_float::OrderedFloat;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Style},
+};
+
+use crate::{
+    Float, Overlay,
+    braille::BrailleCanvas,
+    candlestick_chart::Column,
+    y_axis::{Numeric, YAxis},
+};
+
+const UNICODE_GUIDE_LINE: &str = "┈";
+
+/// An extra pane stacked below the price area, e.g. for RSI or MACD.
+///
+/// A pane shares the columns and cursor of the candles but has its own y axis, fitted to its
+/// visible values unless a fixed range is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pane {
+    /// Lines, drawn the same way as price overlays
+    lines: Vec<Overlay>,
+    /// Bars drawn from zero, colored bullish above and bearish below
+    histogram: Vec<(i64, Float)>,
+    /// y axis scale/precision
+    numeric: Numeric,
+    /// Height relative to the price area
+    pub(crate) height_ratio: u16,
+    /// Fixed y axis range
+    range: Option<(Float, Float)>,
+    /// Horizontal guide lines, e.g. 30/70 for RSI
+    guide_lines: Vec<Float>,
+    guide_line_color: Color,
+}
+
+impl Default for Pane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pane {
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::default(),
+            histogram: Vec::default(),
+            numeric: Numeric::default(),
+            height_ratio: 1,
+            range: None,
+            guide_lines: Vec::default(),
+            guide_line_color: Color::DarkGray,
+        }
+    }
+
+    pub fn line(mut self, line: Overlay) -> Self {
+        self.lines.push(line);
+        self
+    }
+
+    pub fn histogram(mut self, values: Vec<(i64, f64)>) -> Self {
+        self.histogram = values
+            .into_iter()
+            .filter(|(_, value)| value.is_finite())
+            .map(|(timestamp, value)| (timestamp, OrderedFloat::from(value)))
+            .collect();
+        self.histogram.sort_by_key(|(timestamp, _)| *timestamp);
+        self
+    }
+
+    pub fn numeric(mut self, numeric: Numeric) -> Self {
+        self.numeric = numeric;
+        self
+    }
+
+    pub fn height_ratio(mut self, ratio: u16) -> Self {
+        self.height_ratio = ratio;
+        self
+    }
+
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        assert!(min < max);
+        self.range = Some((OrderedFloat::from(min), OrderedFloat::from(max)));
+        self
+    }
+
+    pub fn guide_lines(mut self, values: Vec<f64>) -> Self {
+        self.guide_lines = values.into_iter().map(OrderedFloat::from).collect();
+        self
+    }
+
+    pub fn guide_line_color(mut self, color: Color) -> Self {
+        self.guide_line_color = color;
+        self
+    }
+
+    /// Range of the values between `start` and `end`, or the fixed range when set
+    pub(crate) fn value_range(&self, start: i64, end: i64) -> Option<(Float, Float)> {
+        if self.range.is_some() {
+            return self.range;
+        }
+
+        let histogram = self
+            .histogram
+            .iter()
+            .filter(|(timestamp, _)| *timestamp >= start && *timestamp <= end)
+            .map(|(_, value)| *value);
+        // histogram bars start at zero
+        let zero = (!self.histogram.is_empty()).then_some(Float::default());
+        let values = self
+            .lines
+            .iter()
+            .flat_map(|line| line.visible_values(start, end))
+            .chain(histogram)
+            .chain(zero)
+            .collect::<Vec<_>>();
+
+        let min = *values.iter().min()?;
+        let max = *values.iter().max()?;
+        if min == max {
+            Some((min - 1., max + 1.))
+        } else {
+            Some((min, max))
+        }
+    }
+
+    pub(crate) fn estimated_width(&self) -> u16 {
+        self.value_range(i64::MIN, i64::MAX)
+            .map(|(min, max)| YAxis::estimated_width(self.numeric.clone(), min, max))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn y_axis(&self, height: u16, start: i64, end: i64) -> Option<YAxis> {
+        let (min, max) = self.value_range(start, end)?;
+        Some(YAxis::new(self.numeric.clone(), height, min, max))
+    }
+
+    pub(crate) fn render(
+        &self,
+        columns: &[Column],
+        y_axis: &YAxis,
+        colors: (Color, Color),
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let mut canvas = BrailleCanvas::new(area.width, area.height);
+        let dot_y = |value: Float| (*y_axis.calc_y(value) * 4.) as i64;
+
+        for value in &self.guide_lines {
+            let row = dot_y(*value) / 4;
+            if row < 0 || row >= area.height as i64 {
+                continue;
+            }
+            let cell_y = area.bottom() - 1 - row as u16;
+            for cell_x in area.left()..area.right() {
+                if let Some(cell) = buf.cell_mut((cell_x, cell_y)) {
+                    cell.set_symbol(UNICODE_GUIDE_LINE)
+                        .set_style(Style::default().fg(self.guide_line_color));
+                }
+            }
+        }
+
+        let (bullish_color, bearish_color) = colors;
+        let zero = dot_y(Float::default()).clamp(0, canvas.dot_height() - 1);
+        for (timestamp, value) in &self.histogram {
+            let Some(index) = Column::find(columns, *timestamp) else {
+                continue;
+            };
+            let column = &columns[index];
+            let color = if *value >= Float::default() {
+                bullish_color
+            } else {
+                bearish_color
+            };
+            let top = dot_y(*value).clamp(0, canvas.dot_height() - 1);
+
+            // leave a half cell gap between wide bars, like candle bodies
+            let (first, last) = if column.width == 1 {
+                (column.x as i64 * 2, column.x as i64 * 2 + 1)
+            } else {
+                (
+                    column.x as i64 * 2 + 1,
+                    (column.x + column.width) as i64 * 2 - 2,
+                )
+            };
+            for x in first..=last {
+                canvas.line((x, zero), (x, top), color);
+            }
+        }
+
+        for line in &self.lines {
+            line.render(columns, y_axis, &mut canvas);
+        }
+
+        canvas.render(area, buf);
+    }
+}
@@ -0,0 +1,132 @@
+use std::cmp::{max, min};
+
+use ordered_float::OrderedFloat;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Modifier, Style},
+};
+
+use crate::{Float, symbols::UNICODE_VOID, y_axis::YAxis};
+
+/// A horizontal line across the price area at a fixed price, e.g. an entry or a stop loss.
+///
+/// The line fills the empty cells of its row and is tagged on the y axis with its label, or its
+/// price when it has none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceLine {
+    price: Float,
+    style: Style,
+    label: Option<String>,
+    symbol: char,
+}
+
+impl PriceLine {
+    pub fn new(price: f64) -> Self {
+        Self {
+            price: OrderedFloat::from(price),
+            style: Style::default().fg(Color::DarkGray),
+            label: None,
+            symbol: '─',
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Glyph repeated along the line, `─` by default
+    pub fn symbol(mut self, symbol: char) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    pub(crate) fn render(&self, y_axis: &YAxis, area: Rect, buf: &mut Buffer) {
+        let Some(row) = y_axis.visible_row(self.price) else {
+            return;
+        };
+        for x in area.left()..area.right() {
+            if let Some(cell) = buf.cell_mut((x, area.y + row))
+                && cell.symbol() == UNICODE_VOID
+            {
+                cell.set_char(self.symbol).set_style(self.style);
+            }
+        }
+    }
+
+    /// Row, text and style of the y axis tag, `None` when the price is not visible
+    pub(crate) fn tag(&self, y_axis: &YAxis) -> Option<(u16, String, Style)> {
+        let row = y_axis.visible_row(self.price)?;
+        let text = match &self.label {
+            Some(label) => label.clone(),
+            None => y_axis.label(self.price).trim_start().to_string(),
+        };
+        Some((row, text, self.style.add_modifier(Modifier::REVERSED)))
+    }
+}
+
+/// A shaded zone between two prices across the price area, e.g. a take profit range.
+///
+/// Only the background of the zone is styled, so candles stay visible on top of it. A label is
+/// tagged on the y axis at the upper edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceBand {
+    low: Float,
+    high: Float,
+    style: Style,
+    label: Option<String>,
+}
+
+impl PriceBand {
+    /// The prices may be given in any order
+    pub fn new(from: f64, to: f64) -> Self {
+        let (from, to) = (OrderedFloat::from(from), OrderedFloat::from(to));
+        Self {
+            low: min(from, to),
+            high: max(from, to),
+            style: Style::default().bg(Color::Rgb(40, 40, 40)),
+            label: None,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Top and bottom rows of the part of the band inside the axis range
+    fn rows(&self, y_axis: &YAxis) -> Option<(u16, u16)> {
+        let (axis_min, axis_max) = y_axis.range();
+        if self.high < axis_min || self.low > axis_max {
+            return None;
+        }
+        let top = y_axis.visible_row(min(self.high, axis_max))?;
+        let bottom = y_axis.visible_row(max(self.low, axis_min))?;
+        Some((top, bottom))
+    }
+
+    pub(crate) fn render(&self, y_axis: &YAxis, area: Rect, buf: &mut Buffer) {
+        let Some((top, bottom)) = self.rows(y_axis) else {
+            return;
+        };
+        let rows = Rect::new(area.x, area.y + top, area.width, bottom - top + 1);
+        buf.set_style(rows.intersection(area), self.style);
+    }
+
+    /// Row, text and style of the y axis tag, `None` without a label or when not visible
+    pub(crate) fn tag(&self, y_axis: &YAxis) -> Option<(u16, String, Style)> {
+        let label = self.label.clone()?;
+        let (top, _) = self.rows(y_axis)?;
+        Some((top, label, self.style))
+    }
+}
@@ -0,0 +1,456 @@
+//! Charts built from price moves alone, ignoring time.
+//!
+//! Each builder is fed candles or traded prices one at a time and turns them into columns that
+//! only appear once the price has moved far enough. The columns are returned as candles keyed by
+//! their index, to be drawn by a chart with [`indexed`](crate::CandleStickChart::indexed) set.
+
+use ordered_float::OrderedFloat;
+
+use crate::{
+    Candle,
+    indicators::{Atr, Indicator},
+};
+
+pub trait PriceOnlyChart {
+    /// Feed the next candle
+    fn push(&mut self, candle: &Candle);
+
+    /// Feed the next price traded at `timestamp`
+    fn push_price(&mut self, timestamp: i64, price: f64) {
+        if let Some(candle) = Candle::new(timestamp, price, price, price, price) {
+            self.push(&candle);
+        }
+    }
+
+    /// Columns built so far, as candles with their index as timestamp
+    fn candles(&self) -> Vec<Candle>;
+
+    /// Feed all candles in order and return the columns
+    fn compute(&mut self, candles: &[Candle]) -> Vec<Candle> {
+        for candle in candles {
+            self.push(candle);
+        }
+        self.candles()
+    }
+}
+
+/// Column from `from` to `to`, bullish when `rising`, keyed by `index`
+fn column(index: usize, from: f64, to: f64, rising: bool) -> Candle {
+    let (low, high) = (from.min(to), from.max(to));
+    let (open, close) = if rising { (low, high) } else { (high, low) };
+    Candle {
+        timestamp: index as i64,
+        open: OrderedFloat::from(open),
+        high: OrderedFloat::from(high),
+        low: OrderedFloat::from(low),
+        close: OrderedFloat::from(close),
+        volume: None,
+    }
+}
+
+/// Price move a Renko brick stands for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoxSize {
+    Fixed(f64),
+    /// Average true range over the first `period` candles, then kept for all bricks
+    Atr(usize),
+}
+
+/// Renko bricks of the closes. A brick is added for every box size the close moves beyond the
+/// last brick; turning around takes two box sizes, from the far end of the last brick.
+#[derive(Debug, Clone)]
+pub struct Renko {
+    size: Option<f64>,
+    atr: Option<Atr>,
+    /// First close, where the bricks start
+    base: Option<f64>,
+    /// Open and close of each brick
+    bricks: Vec<(f64, f64)>,
+}
+
+impl Renko {
+    pub fn new(box_size: BoxSize) -> Self {
+        let (size, atr) = match box_size {
+            BoxSize::Fixed(size) => {
+                assert!(size > 0.);
+                (Some(size), None)
+            }
+            BoxSize::Atr(period) => (None, Some(Atr::new(period))),
+        };
+        Self {
+            size,
+            atr,
+            base: None,
+            bricks: Vec::new(),
+        }
+    }
+}
+
+impl PriceOnlyChart for Renko {
+    fn push(&mut self, candle: &Candle) {
+        let close = *candle.close;
+        let base = *self.base.get_or_insert(close);
+        if self.size.is_none()
+            && let Some(atr) = &mut self.atr
+        {
+            self.size = atr.next(candle).filter(|size| *size > 0.);
+        }
+        let Some(size) = self.size else {
+            return;
+        };
+
+        loop {
+            let (top, bottom) = match self.bricks.last() {
+                Some((open, close)) => (open.max(*close), open.min(*close)),
+                None => (base, base),
+            };
+            if close >= top + size {
+                self.bricks.push((top, top + size));
+            } else if close <= bottom - size {
+                self.bricks.push((bottom, bottom - size));
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn candles(&self) -> Vec<Candle> {
+        self.bricks
+            .iter()
+            .enumerate()
+            .map(|(index, (open, close))| column(index, *open, *close, close > open))
+            .collect()
+    }
+}
+
+/// Kagi lines of the closes, drawn with [`ChartStyle::Kagi`](crate::ChartStyle::Kagi). A line
+/// follows the price until it turns by the reversal amount, then a new line starts at the turn.
+/// Lines are bullish (yang) from rising above the previous peak until falling below the previous
+/// trough, and bearish (yin) from then on.
+#[derive(Debug, Clone)]
+pub struct Kagi {
+    reversal: f64,
+    /// First close, where the first line starts
+    base: Option<f64>,
+    /// Start and end of each line
+    lines: Vec<(f64, f64)>,
+}
+
+impl Kagi {
+    pub fn new(reversal: f64) -> Self {
+        assert!(reversal > 0.);
+        Self {
+            reversal,
+            base: None,
+            lines: Vec::new(),
+        }
+    }
+}
+
+impl PriceOnlyChart for Kagi {
+    fn push(&mut self, candle: &Candle) {
+        let close = *candle.close;
+        let base = *self.base.get_or_insert(close);
+        let Some((start, end)) = self.lines.last_mut() else {
+            if close != base {
+                self.lines.push((base, close));
+            }
+            return;
+        };
+
+        let rising = *end > *start;
+        if (rising && close > *end) || (!rising && close < *end) {
+            *end = close;
+        } else if (close - *end).abs() >= self.reversal {
+            let turn = *end;
+            self.lines.push((turn, close));
+        }
+    }
+
+    fn candles(&self) -> Vec<Candle> {
+        let mut yang = false;
+        let (mut peak, mut trough) = (None, None);
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, (start, end))| {
+                let rising = end > start;
+                if index == 0 {
+                    // the start of the first line is the first peak or trough
+                    yang = rising;
+                    if rising {
+                        trough = Some(*start);
+                    } else {
+                        peak = Some(*start);
+                    }
+                } else if rising && peak.is_some_and(|peak| *end > peak) {
+                    yang = true;
+                } else if !rising && trough.is_some_and(|trough| *end < trough) {
+                    yang = false;
+                }
+                if rising {
+                    peak = Some(*end);
+                } else {
+                    trough = Some(*end);
+                }
+                column(index, *start, *end, yang)
+            })
+            .collect()
+    }
+}
+
+/// Line break columns of the closes, three-line break by default. A close beyond the last line
+/// in its direction adds a line; turning around takes a close beyond every one of the last
+/// `lines` lines.
+#[derive(Debug, Clone)]
+pub struct LineBreak {
+    lines: usize,
+    /// First close, where the first line starts
+    base: Option<f64>,
+    /// Open and close of each line
+    columns: Vec<(f64, f64)>,
+}
+
+impl LineBreak {
+    pub fn new(lines: usize) -> Self {
+        assert!(lines > 0);
+        Self {
+            lines,
+            base: None,
+            columns: Vec::new(),
+        }
+    }
+}
+
+impl Default for LineBreak {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl PriceOnlyChart for LineBreak {
+    fn push(&mut self, candle: &Candle) {
+        let close = *candle.close;
+        let base = *self.base.get_or_insert(close);
+        let Some(&(open, last)) = self.columns.last() else {
+            if close != base {
+                self.columns.push((base, close));
+            }
+            return;
+        };
+
+        let rising = last > open;
+        let recent = &self.columns[self.columns.len().saturating_sub(self.lines)..];
+        let highest = recent
+            .iter()
+            .map(|(o, c)| o.max(*c))
+            .fold(f64::MIN, f64::max);
+        let lowest = recent
+            .iter()
+            .map(|(o, c)| o.min(*c))
+            .fold(f64::MAX, f64::min);
+        if (rising && close > last) || (!rising && close < last) {
+            self.columns.push((last, close));
+        } else if (rising && close < lowest) || (!rising && close > highest) {
+            self.columns.push((open, close));
+        }
+    }
+
+    fn candles(&self) -> Vec<Candle> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, (open, close))| column(index, *open, *close, close > open))
+            .collect()
+    }
+}
+
+/// Point-and-figure columns of the highs and lows, drawn with
+/// [`ChartStyle::PointAndFigure`](crate::ChartStyle::PointAndFigure). A column of X rises while
+/// the highs fill new boxes above it and a column of O falls while the lows fill new boxes below
+/// it; turning around takes `reversal` boxes.
+#[derive(Debug, Clone)]
+pub struct PointAndFigure {
+    box_size: f64,
+    reversal: i64,
+    /// Box of the first close, where the first column starts
+    base: Option<i64>,
+    /// Lowest and highest box of each column, and whether it rises
+    columns: Vec<(i64, i64, bool)>,
+    /// Timestamp of the candle starting each column
+    timestamps: Vec<i64>,
+}
+
+impl PointAndFigure {
+    pub fn new(box_size: f64, reversal: usize) -> Self {
+        assert!(box_size > 0. && reversal > 0);
+        Self {
+            box_size,
+            reversal: reversal as i64,
+            base: None,
+            columns: Vec::new(),
+            timestamps: Vec::new(),
+        }
+    }
+
+    /// Timestamp of the candle starting each column, for
+    /// [`index_timestamps`](crate::CandleStickChart::index_timestamps)
+    pub fn timestamps(&self) -> Vec<i64> {
+        self.timestamps.clone()
+    }
+
+    /// Highest box reached by `price`, counting boxes from zero
+    fn box_above(&self, price: f64) -> i64 {
+        (price / self.box_size + 1e-9).floor() as i64
+    }
+
+    /// Lowest box reached by `price`
+    fn box_below(&self, price: f64) -> i64 {
+        (price / self.box_size - 1e-9).ceil() as i64
+    }
+}
+
+impl PriceOnlyChart for PointAndFigure {
+    fn push(&mut self, candle: &Candle) {
+        let (high, low) = (self.box_above(*candle.high), self.box_below(*candle.low));
+        let base = *self.base.get_or_insert(self.box_above(*candle.close));
+        let column = match self.columns.last_mut() {
+            None if high > base => (base, high, true),
+            None if low < base => (low, base, false),
+            None => return,
+            Some((_, top, true)) if high > *top => {
+                *top = high;
+                return;
+            }
+            Some((bottom, _, false)) if low < *bottom => {
+                *bottom = low;
+                return;
+            }
+            Some((_, top, true)) if *top - low >= self.reversal => (low, *top - 1, false),
+            Some((bottom, _, false)) if high - *bottom >= self.reversal => {
+                (*bottom + 1, high, true)
+            }
+            Some(_) => return,
+        };
+        self.columns.push(column);
+        self.timestamps.push(candle.timestamp);
+    }
+
+    fn candles(&self) -> Vec<Candle> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, (bottom, top, rising))| {
+                let (low, high) = (*bottom as f64, (*top + 1) as f64);
+                column(index, low * self.box_size, high * self.box_size, *rising)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoxSize, Kagi, LineBreak, PointAndFigure, PriceOnlyChart, Renko};
+    use crate::Candle;
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle::new(i as i64 * 60000, c, c + 1., c - 1., c).unwrap())
+            .collect()
+    }
+
+    fn open_close(candles: Vec<Candle>) -> Vec<(i64, f64, f64)> {
+        candles
+            .iter()
+            .map(|c| (c.timestamp, *c.open, *c.close))
+            .collect()
+    }
+
+    #[test]
+    fn renko() {
+        let bricks = Renko::new(BoxSize::Fixed(1.)).compute(&candles(&[10., 12.5, 11.8, 10.9, 9.]));
+        // two bricks up, no turn until two boxes down from 12, then two bricks down
+        assert_eq!(
+            open_close(bricks),
+            vec![(0, 10., 11.), (1, 11., 12.), (2, 11., 10.), (3, 10., 9.)]
+        );
+
+        // the true range of every candle is 2
+        let mut renko = Renko::new(BoxSize::Atr(2));
+        renko.compute(&candles(&[10., 10., 13.]));
+        assert_eq!(open_close(renko.candles()), vec![(0, 10., 12.)]);
+    }
+
+    #[test]
+    fn kagi() {
+        let lines = Kagi::new(2.).compute(&candles(&[10., 12., 11., 14., 11., 9., 13.]));
+        // the dip to 11 is too small to turn, yin below the start at 10 until above the peak
+        assert_eq!(
+            open_close(lines),
+            vec![(0, 10., 14.), (1, 14., 9.), (2, 13., 9.)]
+        );
+
+        let lines = Kagi::new(2.).compute(&candles(&[10., 14., 11., 13., 10., 15.]));
+        assert_eq!(
+            open_close(lines),
+            vec![
+                (0, 10., 14.),
+                (1, 11., 14.),
+                (2, 11., 13.),
+                (3, 13., 10.),
+                (4, 10., 15.)
+            ]
+        );
+    }
+
+    #[test]
+    fn point_and_figure() {
+        let candles = [
+            (10.5, 10.5, 9.5),
+            (12.5, 13.2, 11.),
+            (10.5, 12.5, 9.8),
+            (12.5, 12.9, 12.),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(close, high, low))| {
+            Candle::new(i as i64 * 60000, close, high, low, close).unwrap()
+        })
+        .collect::<Vec<_>>();
+        let mut point_and_figure = PointAndFigure::new(1., 3);
+        // X from 10 to 13, O down to 10 after a 3 box turn, and no turn up to 13 yet
+        assert_eq!(
+            open_close(point_and_figure.compute(&candles)),
+            vec![(0, 10., 14.), (1, 13., 10.)]
+        );
+        assert_eq!(point_and_figure.timestamps(), vec![60000, 120000]);
+
+        // traded prices keep their timestamps for the month labels
+        let mut point_and_figure = PointAndFigure::new(1., 3);
+        for (timestamp, price) in [(1000, 10.5), (2000, 12.2), (3000, 8.5)] {
+            point_and_figure.push_price(timestamp, price);
+        }
+        assert_eq!(point_and_figure.timestamps(), vec![2000, 3000]);
+    }
+
+    #[test]
+    fn line_break() {
+        let mut line_break = LineBreak::default();
+        for (i, price) in [10., 11., 12., 13., 11., 10.5, 14.].into_iter().enumerate() {
+            line_break.push_price(i as i64 * 1000, price);
+        }
+        // 11 and 10.5 stay above the low of the last three lines, 14 adds to the rise
+        assert_eq!(
+            open_close(line_break.candles()),
+            vec![(0, 10., 11.), (1, 11., 12.), (2, 12., 13.), (3, 13., 14.)]
+        );
+
+        let lines = LineBreak::new(1).compute(&candles(&[10., 11., 12., 10.5, 10.]));
+        assert_eq!(
+            open_close(lines),
+            vec![(0, 10., 11.), (1, 11., 12.), (2, 11., 10.5), (3, 10.5, 10.)]
+        );
+    }
+}
@@ -1,10 +1,50 @@
 pub const UNICODE_VOID: &str = " ";
 pub const UNICODE_BODY: &str = "┃";
 pub const UNICODE_WICK: &str = "│";
+pub const UNICODE_UPPER_BODY: &str = "╽";
+pub const UNICODE_LOWER_BODY: &str = "╿";
 pub const UNICODE_HALF_BODY_BOTTOM: &str = "╻";
 pub const UNICODE_HALF_WICK_BOTTOM: &str = "╷";
 pub const UNICODE_HALF_BODY_TOP: &str = "╹";
 pub const UNICODE_HALF_WICK_TOP: &str = "╵";
+pub const UNICODE_CURSOR: &str = "┊";
+pub const UNICODE_LAST_PRICE: &str = "┄";
+
+// OHLC bar symbols, open ticks point left and close ticks right
+pub const UNICODE_BAR_OPEN: &str = "┤";
+pub const UNICODE_BAR_CLOSE: &str = "├";
+pub const UNICODE_BAR_OPEN_CLOSE: &str = "┼";
+pub const UNICODE_BAR_TICK: &str = "─";
+pub const UNICODE_BAR_TICK_LEFT_END: &str = "╶";
+pub const UNICODE_BAR_TICK_RIGHT_END: &str = "╴";
+
+// Hollow body symbols, outlining the bodies of rising candles
+pub const UNICODE_HOLLOW_BODY: &str = "║";
+pub const UNICODE_HOLLOW_BODY_TOP: &str = "╥";
+pub const UNICODE_HOLLOW_BODY_BOTTOM: &str = "╨";
+pub const UNICODE_HOLLOW_TOP_LEFT: &str = "┌";
+pub const UNICODE_HOLLOW_TOP_RIGHT: &str = "┐";
+pub const UNICODE_HOLLOW_BOTTOM_LEFT: &str = "└";
+pub const UNICODE_HOLLOW_BOTTOM_RIGHT: &str = "┘";
+pub const UNICODE_HOLLOW_LEFT: &str = "├";
+pub const UNICODE_HOLLOW_RIGHT: &str = "┤";
+pub const UNICODE_HOLLOW_EDGE: &str = "─";
+pub const UNICODE_HOLLOW_WICK_ABOVE: &str = "┴";
+pub const UNICODE_HOLLOW_WICK_BELOW: &str = "┬";
+pub const UNICODE_HOLLOW_WICK_THROUGH: &str = "┼";
+
+// Point-and-figure boxes of rising and falling columns
+pub const UNICODE_X_BOX: &str = "X";
+pub const UNICODE_O_BOX: &str = "O";
+
+// Kagi lines indexed by the directions they join: up 1, down 2, left 4 and right 8. Light for
+// yin lines, heavy for yang lines.
+pub const UNICODE_KAGI_LIGHT: [&str; 16] = [
+    "│", "╵", "╷", "│", "╴", "┘", "┐", "┤", "╶", "└", "┌", "├", "─", "┴", "┬", "┼",
+];
+pub const UNICODE_KAGI_HEAVY: [&str; 16] = [
+    "┃", "╹", "╻", "┃", "╸", "┛", "┓", "┫", "╺", "┗", "┏", "┣", "━", "┻", "┳", "╋",
+];
 
 // Block symbols for stretching
 pub const UNICODE_LEFT_HALF_BLOCK: &str = "▌";
@@ -12,3 +52,6 @@ pub const UNICODE_RIGHT_HALF_BLOCK: &str = "▐";
 pub const UNICODE_LEFT_EIGHTH_BLOCK: &str = "▏";
 pub const UNICODE_RIGHT_EIGHTH_BLOCK: &str = "▕";
 pub const UNICODE_FULL_BLOCK: &str = "█";
+
+// Lower block symbols for bars, indexed by eighths of a cell
+pub const UNICODE_LOWER_BLOCKS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
@@ -0,0 +1,85 @@
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+use crate::{Candle, y_axis::Numeric};
+
+/// Where the OHLC info box of the cursor candle is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TooltipPosition {
+    /// Next to the cursor, on the side with enough room
+    Floating,
+    /// Docked to the top left corner of the price area
+    TopLeft,
+    /// Docked to the top right corner of the price area
+    TopRight,
+}
+
+pub(crate) struct Tooltip<'a> {
+    candle: &'a Candle,
+    numeric: &'a Numeric,
+    style: Style,
+}
+
+impl<'a> Tooltip<'a> {
+    pub fn new(candle: &'a Candle, numeric: &'a Numeric, style: Style) -> Self {
+        Self {
+            candle,
+            numeric,
+            style,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let format =
+            |label: &str, value| format!("{} {}", label, self.numeric.format(value).trim());
+        let mut lines = vec![
+            format("O", self.candle.open),
+            format("H", self.candle.high),
+            format("L", self.candle.low),
+            format("C", self.candle.close),
+        ];
+        if let Some(volume) = self.candle.volume {
+            lines.push(format("V", volume));
+        }
+
+        lines
+    }
+
+    /// Draw the box inside `area`, next to the cursor column `cursor_x` when floating
+    pub fn render(&self, position: TooltipPosition, cursor_x: u16, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let width = lines
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or_default() as u16
+            + 2;
+        let height = lines.len() as u16 + 2;
+        if width > area.width || height > area.height {
+            return;
+        }
+
+        let x = match position {
+            TooltipPosition::TopLeft => area.x,
+            TooltipPosition::TopRight => area.right() - width,
+            TooltipPosition::Floating => {
+                if cursor_x + 2 + width <= area.right() {
+                    cursor_x + 2
+                } else {
+                    cursor_x.saturating_sub(width + 1).max(area.x)
+                }
+            }
+        };
+        let rect = Rect::new(x, area.y, width, height);
+
+        Clear.render(rect, buf);
+        Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+            .block(Block::bordered())
+            .style(self.style)
+            .render(rect, buf);
+    }
+}
@@ -1,6 +1,11 @@
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use itertools::Itertools;
 
+use crate::candlestick_chart_state::Scale;
+
+/// Columns between two labels of an indexed axis
+const INDEX_LABEL_GAP: i64 = 10;
+
 enum Precision {
     Second,
     Minute,
@@ -75,6 +80,9 @@ pub(crate) struct XAxis {
     max: i64,
     interval: Interval,
     is_realtime: bool,
+    scale: Scale,
+    indexed: bool,
+    index_timestamps: Vec<i64>,
 }
 
 impl XAxis {
@@ -87,9 +95,39 @@ impl XAxis {
             max,
             interval,
             is_realtime,
+            scale: Scale {
+                merge: 1,
+                column_width: 1,
+            },
+            indexed: false,
+            index_timestamps: Vec::new(),
         }
     }
 
+    /// Bounds are consecutive indices instead of timestamps, labeled by their number
+    pub fn indexed(mut self, indexed: bool) -> Self {
+        self.indexed = indexed;
+        self
+    }
+
+    /// Timestamp of each index, to label the indices starting a new month instead
+    pub fn index_timestamps(mut self, timestamps: Vec<i64>) -> Self {
+        self.index_timestamps = timestamps;
+        self
+    }
+
+    /// Columns spanning several intervals or several cells, when zoomed
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Cell offset of the center of the column at `index`
+    fn position(&self, index: usize) -> usize {
+        let width = self.scale.column_width as usize;
+        self.scale.offset(self.width) as usize + index * width + (width - 1) / 2
+    }
+
     /// render priority
     ///
     /// 1. second diff      -> HH:MM:SS
@@ -99,6 +137,9 @@ impl XAxis {
     ///
     /// worst case: last one is "YYYY-mm-dd HH:MM:SS"(19 chars)
     pub fn render(&self, time_offset: FixedOffset) -> Vec<String> {
+        if self.indexed {
+            return self.render_indexed(time_offset);
+        }
         let width = self.width as usize;
 
         let mut result = vec![
@@ -106,19 +147,21 @@ impl XAxis {
             " ".repeat(width).chars().collect_vec(),
         ];
 
+        let step = self.interval as i64 * 1000 * self.scale.merge;
         let full_timestamps = (self.min..=self.max)
-            .step_by(self.interval as usize * 1000)
+            .step_by(step as usize)
             .map(|t| {
                 let naive = DateTime::from_timestamp_millis(t).unwrap().naive_local();
                 (t, Utc.from_utc_datetime(&naive))
             })
             .collect_vec();
         let full_timestamps_len = full_timestamps.len();
-        let timestamps = if full_timestamps_len > width {
+        let columns = self.scale.columns(self.width) as usize;
+        let timestamps = if full_timestamps_len > columns {
             full_timestamps
                 .into_iter()
-                .skip(full_timestamps_len - width)
-                .take(width)
+                .skip(full_timestamps_len - columns)
+                .take(columns)
                 .collect_vec()
         } else {
             full_timestamps
@@ -141,12 +184,12 @@ impl XAxis {
 
                 let written = overwrite_chars(
                     &mut result[1],
-                    (timestamp_len - 1) as isize - (rendered.len() / 2) as isize,
+                    self.position(timestamp_len - 1) as isize - (rendered.len() / 2) as isize,
                     rendered,
                     true,
                 );
                 if written {
-                    result[0][timestamp_len - 1] = '┴';
+                    result[0][self.position(timestamp_len - 1)] = '┴';
                 }
             }
             2.. => {
@@ -167,16 +210,16 @@ impl XAxis {
                     };
                     let written = overwrite_chars(
                         &mut result[1],
-                        (timestamp_len - 1) as isize - (rendered.len() / 2) as isize,
+                        self.position(timestamp_len - 1) as isize - (rendered.len() / 2) as isize,
                         rendered,
                         true,
                     );
                     if written {
-                        result[0][timestamp_len - 1] = '┴';
+                        result[0][self.position(timestamp_len - 1)] = '┴';
                     }
                 }
 
-                let gap = self.interval.render_gap() as i64 * (self.interval as i64) * 1000;
+                let gap = self.interval.render_gap() as i64 * step;
                 for (idx, ((_, prev), (timestamp, now))) in
                     timestamps.into_iter().tuple_windows().enumerate()
                 {
@@ -187,13 +230,13 @@ impl XAxis {
                     let rendered = diff_datetime_string(prev, now, time_offset);
                     let written = overwrite_chars(
                         &mut result[1],
-                        idx as isize - (rendered.len() / 2) as isize,
+                        self.position(idx + 1) as isize - 1 - (rendered.len() / 2) as isize,
                         format!(" {} ", rendered),
                         false,
                     );
 
                     if written {
-                        result[0][idx + 1] = '┴';
+                        result[0][self.position(idx + 1)] = '┴';
                     }
                 }
             }
@@ -201,6 +244,116 @@ impl XAxis {
 
         result.into_iter().map(String::from_iter).collect()
     }
+
+    /// The last index, then every index that is a multiple of `INDEX_LABEL_GAP` columns
+    fn render_indexed(&self, time_offset: FixedOffset) -> Vec<String> {
+        let width = self.width as usize;
+        let mut result = vec![
+            "─".repeat(width).chars().collect_vec(),
+            " ".repeat(width).chars().collect_vec(),
+        ];
+
+        let step = self.scale.merge;
+        let indices = (self.min..=self.max).step_by(step as usize).collect_vec();
+        let columns = self.scale.columns(self.width) as usize;
+        let indices = &indices[indices.len().saturating_sub(columns)..];
+        if !self.index_timestamps.is_empty() {
+            self.render_months(&mut result, indices, time_offset);
+            return result.into_iter().map(String::from_iter).collect();
+        }
+        let Some((&last, rest)) = indices.split_last() else {
+            return result.into_iter().map(String::from_iter).collect();
+        };
+
+        if last >= 0 {
+            let rendered = if self.is_realtime {
+                format!("*{}", last)
+            } else {
+                last.to_string()
+            };
+            let position = self.position(rest.len());
+            let written = overwrite_chars(
+                &mut result[1],
+                position as isize - (rendered.len() / 2) as isize,
+                rendered,
+                true,
+            );
+            if written {
+                result[0][position] = '┴';
+            }
+        }
+
+        let gap = INDEX_LABEL_GAP * step;
+        for (idx, index) in rest.iter().enumerate() {
+            if *index < 0 || index % gap != 0 {
+                continue;
+            }
+            let rendered = index.to_string();
+            let written = overwrite_chars(
+                &mut result[1],
+                self.position(idx) as isize - 1 - (rendered.len() / 2) as isize,
+                format!(" {} ", rendered),
+                false,
+            );
+            if written {
+                result[0][self.position(idx)] = '┴';
+            }
+        }
+
+        result.into_iter().map(String::from_iter).collect()
+    }
+
+    /// `YYYY/mm` under the indices whose timestamp falls into another month than the one before
+    fn render_months(&self, result: &mut [Vec<char>], indices: &[i64], time_offset: FixedOffset) {
+        let month = |index: i64| self.index_month(index, time_offset);
+        for (idx, index) in indices.iter().enumerate() {
+            let Some(rendered) = month(*index) else {
+                continue;
+            };
+            if *index > 0 && month(index - 1).as_ref() == Some(&rendered) {
+                continue;
+            }
+            let written = overwrite_chars(
+                &mut result[1],
+                self.position(idx) as isize - 1 - (rendered.len() / 2) as isize,
+                format!(" {} ", rendered),
+                false,
+            );
+            if written {
+                result[0][self.position(idx)] = '┴';
+            }
+        }
+    }
+
+    /// Full date/time of `timestamp` at the precision of the interval, e.g. for the cursor
+    /// Month of the timestamp of `index`, if there is one
+    fn index_month(&self, index: i64, time_offset: FixedOffset) -> Option<String> {
+        let timestamp = *self.index_timestamps.get(usize::try_from(index).ok()?)?;
+        let datetime = DateTime::from_timestamp_millis(timestamp)?;
+        Some(
+            datetime
+                .with_timezone(&time_offset)
+                .format("%Y/%m")
+                .to_string(),
+        )
+    }
+
+    pub fn label(&self, timestamp: i64, time_offset: FixedOffset) -> String {
+        if self.indexed {
+            return self
+                .index_month(timestamp, time_offset)
+                .unwrap_or_else(|| timestamp.to_string());
+        }
+        let datetime = DateTime::from_timestamp_millis(timestamp)
+            .unwrap()
+            .with_timezone(&time_offset);
+        match self.interval.render_precision() {
+            Precision::Second => datetime.format("%Y/%m/%d %H:%M:%S"),
+            Precision::Minute => datetime.format("%Y/%m/%d %H:%M"),
+            Precision::Day => datetime.format("%Y/%m/%d"),
+        }
+        .to_string()
+    }
 }
 
 fn shorted_now_string<Tz: TimeZone>(
@@ -319,7 +472,7 @@ mod tests {
     use itertools::Itertools;
 
     use super::XAxis;
-    use crate::x_axis::{overwrite_chars, Interval};
+    use crate::x_axis::{Interval, overwrite_chars};
 
     #[test]
     fn test_overwrite_chars() {
@@ -360,4 +513,41 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn render_indexed() {
+        let axis = XAxis::new(30, 3, 32, Interval::OneMinute, false).indexed(true);
+        assert_eq!(
+            axis.render(Utc.fix()),
+            vec![
+                "───────┴─────────┴───────────┴",
+                "      10        20          32"
+            ]
+        );
+        assert_eq!(axis.label(12, Utc.fix()), "12");
+
+        // 2024/01/31 for the first ten indices, then days of February
+        let timestamps = (0..30)
+            .map(|i| 1706659200000 + i / 10 * 86400000)
+            .collect_vec();
+        let axis = XAxis::new(30, 3, 32, Interval::OneMinute, false)
+            .indexed(true)
+            .index_timestamps(timestamps);
+        assert_eq!(
+            axis.render(Utc.fix()),
+            vec![
+                "───────┴──────────────────────",
+                "    2024/02                   "
+            ]
+        );
+        assert_eq!(axis.label(12, Utc.fix()), "2024/02");
+        // past the timestamps
+        assert_eq!(axis.label(32, Utc.fix()), "32");
+    }
+
+    #[test]
+    fn label() {
+        let axis = XAxis::new(30, 1704006060000, 1704009600000, Interval::OneMinute, false);
+        assert_eq!(axis.label(1704009600000, Utc.fix()), "2023/12/31 08:00");
+    }
 }
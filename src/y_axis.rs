@@ -1,15 +1,55 @@
-use std::cmp::{self, max};
+use std::{
+    cmp::{self, max},
+    fmt,
+    ops::RangeInclusive,
+    sync::Arc,
+};
 
 use ordered_float::OrderedFloat;
 
-use crate::Float;
+use crate::{Float, formatter::LabelFormatter};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Upper bound of automatically picked decimals
+const MAX_AUTO_SCALE: f64 = 12.;
+
+/// Number formatting of axis labels: `precision` is the padded width and `scale` the number of
+/// decimals.
+#[derive(Clone)]
 pub struct Numeric {
     precision: usize,
     scale: usize,
+    /// Pick the scale from the labeled range instead
+    auto: bool,
+    /// Custom label text, padded to `precision`
+    formatter: Option<Arc<dyn LabelFormatter>>,
 }
 
+impl fmt::Debug for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Numeric")
+            .field("precision", &self.precision)
+            .field("scale", &self.scale)
+            .field("auto", &self.auto)
+            .field("formatter", &self.formatter.is_some())
+            .finish()
+    }
+}
+
+/// Formatters are compared by identity
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.precision == other.precision
+            && self.scale == other.scale
+            && self.auto == other.auto
+            && match (&self.formatter, &other.formatter) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+    }
+}
+
+impl Eq for Numeric {}
+
 impl Default for Numeric {
     fn default() -> Self {
         Self::new(9, 3)
@@ -18,13 +58,122 @@ impl Default for Numeric {
 
 impl Numeric {
     pub fn new(precision: usize, scale: usize) -> Self {
-        Self { precision, scale }
+        Self {
+            precision,
+            scale,
+            auto: false,
+            formatter: None,
+        }
+    }
+
+    /// Unpadded, with just enough decimals to tell neighboring labels of the visible range apart
+    pub fn auto() -> Self {
+        Self {
+            precision: 0,
+            scale: 0,
+            auto: true,
+            formatter: None,
+        }
+    }
+
+    /// Format labels with e.g. [`SiSuffix`](crate::formatter::SiSuffix) or a closure
+    pub fn formatter(mut self, formatter: impl LabelFormatter + 'static) -> Self {
+        self.formatter = Some(Arc::new(formatter));
+        self
+    }
+
+    /// Unpadded, with the decimals of the minimum price increment, e.g. 8 for `0.00000001`
+    pub fn tick_size(tick_size: f64) -> Self {
+        let scale = tick_size
+            .to_string()
+            .split_once('.')
+            .map_or(0, |(_, decimals)| decimals.len());
+        Self::new(0, scale)
+    }
+
+    pub(crate) fn is_auto(&self) -> bool {
+        self.auto
+    }
+
+    /// Fixed numeric for labels `step` apart. A flat range keeps 5 significant digits of
+    /// `reference` instead.
+    pub(crate) fn resolve(&self, step: Float, reference: Float) -> Numeric {
+        if !self.auto {
+            return self.clone();
+        }
+        let scale = if step > Float::default() {
+            (-step.log10()).ceil()
+        } else if reference != Float::default() {
+            4. - reference.abs().log10().floor()
+        } else {
+            0.
+        };
+        Self {
+            scale: scale.clamp(0., MAX_AUTO_SCALE) as usize,
+            auto: false,
+            ..self.clone()
+        }
     }
 
     pub fn format(&self, value: Float) -> String {
+        if self.auto {
+            return self.resolve(Float::default(), value).format(value);
+        }
         let precision = self.precision;
         let scale = self.scale;
-        format!("{0:>precision$.scale$}", value)
+        match &self.formatter {
+            Some(formatter) => format!("{0:>precision$}", formatter.format(*value, scale)),
+            None => format!("{0:>precision$.scale$}", value),
+        }
+    }
+}
+
+/// Mapping of prices to rows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriceScale {
+    #[default]
+    Linear,
+    /// Equal ratios take equal heights. Falls back to linear when the range is not positive.
+    Logarithmic,
+    /// Linear, labeled with the percent change from the close of the candle at the given
+    /// timestamp, or of the first visible candle
+    Percentage(Option<i64>),
+    /// Linear, labeled as if the close of the candle at the given timestamp, or of the first
+    /// visible candle, was 100
+    Rebased(Option<i64>),
+}
+
+impl PriceScale {
+    /// Label of `value` relative to `base`, plain for absolute scales
+    fn format(&self, numeric: &Numeric, value: Float, base: Option<Float>) -> String {
+        match (self, base) {
+            (PriceScale::Percentage(_), Some(base)) => {
+                format!("{}%", numeric.format((value / base - 1.) * 100.))
+            }
+            (PriceScale::Rebased(_), Some(base)) => numeric.format(value / base * 100.),
+            _ => numeric.format(value),
+        }
+    }
+}
+
+/// Sides of the plot area the y axis is drawn on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum YAxisPosition {
+    #[default]
+    Left,
+    /// Next to the latest candle
+    Right,
+    /// Mirrored on both sides
+    Both,
+}
+
+impl YAxisPosition {
+    pub(crate) fn is_left(&self) -> bool {
+        matches!(self, YAxisPosition::Left | YAxisPosition::Both)
+    }
+
+    pub(crate) fn is_right(&self) -> bool {
+        matches!(self, YAxisPosition::Right | YAxisPosition::Both)
     }
 }
 
@@ -33,12 +182,57 @@ pub(crate) struct YAxis {
     height: u16,
     min: Float,
     max: Float,
+    /// Value per row, in log space for a logarithmic axis
     unit: Float,
+    is_logarithmic: bool,
+    scale: PriceScale,
+    /// Price labels are relative to, for percentage and rebased scales
+    base: Option<Float>,
+    /// Preferred number of rows between two labels
+    tick_spacing: u16,
+}
+
+/// 1, 2 or 5 times a power of ten, nearest to `step` on a logarithmic scale
+fn nice_step(step: f64) -> f64 {
+    let magnitude = 10f64.powf(step.log10().floor());
+    let mantissa = match step / magnitude {
+        m if m < 2f64.sqrt() => 1.,
+        m if m < 10f64.sqrt() => 2.,
+        m if m < 50f64.sqrt() => 5.,
+        _ => 10.,
+    };
+    mantissa * magnitude
+}
+
+/// Multipliers of `step` between `low` and `high`, tolerating rounding errors at both ends
+fn multiples(low: f64, high: f64, step: f64) -> RangeInclusive<i64> {
+    const EPSILON: f64 = 1e-9;
+    (low / step - EPSILON).ceil() as i64..=(high / step + EPSILON).floor() as i64
 }
 
 impl YAxis {
     pub fn estimated_width(numeric: Numeric, min: Float, max: Float) -> u16 {
-        cmp::max(numeric.format(max).len(), numeric.format(min).len()) as u16 + 4
+        cmp::max(
+            numeric.format(max).chars().count(),
+            numeric.format(min).chars().count(),
+        ) as u16
+            + 4
+    }
+
+    /// Width of relative labels when the base can be any price in `bases`
+    pub fn estimated_relative_width(
+        numeric: Numeric,
+        scale: PriceScale,
+        (min, max): (Float, Float),
+        bases: (Float, Float),
+    ) -> u16 {
+        let plain_width = Self::estimated_width(numeric.clone(), min, max);
+        [bases.0, bases.1]
+            .into_iter()
+            .filter(|base| *base > Float::default())
+            .flat_map(|base| [min, max].map(|value| scale.format(&numeric, value, Some(base))))
+            .map(|label| label.chars().count() as u16 + 4)
+            .fold(plain_width, cmp::max)
     }
 
     pub fn new(numeric: Numeric, height: u16, min: Float, max: Float) -> Self {
@@ -51,6 +245,68 @@ impl YAxis {
             min,
             max,
             unit,
+            is_logarithmic: false,
+            scale: PriceScale::default(),
+            base: None,
+            tick_spacing: 4,
+        }
+    }
+
+    /// Preferred number of rows between two labels, 4 by default
+    pub fn tick_spacing(mut self, rows: u16) -> Self {
+        self.tick_spacing = max(rows, 1);
+        self
+    }
+
+    pub fn scale(mut self, scale: PriceScale) -> Self {
+        self.scale = scale;
+        self.is_logarithmic = scale == PriceScale::Logarithmic && self.min > Float::default();
+        self.unit = (self.transform(self.max) - self.transform(self.min))
+            / OrderedFloat::from(self.height as f64);
+        self
+    }
+
+    /// Widen the range so `above` rows stay free over the highest value and `below` rows under
+    /// the lowest one. Ignored when the range would not keep at least one row.
+    pub(crate) fn reserve_rows(mut self, above: u16, below: u16) -> Self {
+        let reserved = above + below;
+        if reserved == 0 || self.max <= self.min || reserved + 1 >= self.height {
+            return self;
+        }
+        let (low, high) = (self.transform(self.min), self.transform(self.max));
+        // half a row more on both sides keeps the extremes off the row boundaries
+        let unit = (high - low) / OrderedFloat::from((self.height - reserved - 1) as f64);
+        self.min = self.inverse(low - unit * OrderedFloat::from(below as f64 + 0.5));
+        self.max = self.inverse(high + unit * OrderedFloat::from(above as f64 + 0.5));
+        self.unit = (self.transform(self.max) - self.transform(self.min))
+            / OrderedFloat::from(self.height as f64);
+        self
+    }
+
+    /// Price that percentage and rebased labels are relative to. Ignored unless positive.
+    pub fn base(mut self, base: Float) -> Self {
+        self.base = (base > Float::default()).then_some(base);
+        self
+    }
+
+    pub(crate) fn label(&self, value: Float) -> String {
+        self.scale.format(&self.label_numeric(), value, self.base)
+    }
+
+    fn transform(&self, value: Float) -> Float {
+        if self.is_logarithmic {
+            // keep values below the range, e.g. wicks of other series, finite
+            OrderedFloat::from(value.0.max(f64::MIN_POSITIVE).ln())
+        } else {
+            value
+        }
+    }
+
+    fn inverse(&self, value: Float) -> Float {
+        if self.is_logarithmic {
+            OrderedFloat::from(value.exp())
+        } else {
+            value
         }
     }
 
@@ -58,20 +314,153 @@ impl YAxis {
         self.height
     }
 
+    /// Lowest and highest value of the axis
+    pub(crate) fn range(&self) -> (Float, Float) {
+        (self.min, self.max)
+    }
+
+    /// Numeric of prices on this axis, with an automatic scale resolved for the labeled range
+    pub fn numeric(&self) -> Numeric {
+        let reference = self.min.abs().max(self.max.abs());
+        self.numeric
+            .resolve(self.tick_step((1., 0.)), OrderedFloat::from(reference))
+    }
+
+    /// Numeric of the labels, which are relative values for percentage and rebased scales
+    fn label_numeric(&self) -> Numeric {
+        let transform = self.label_transform();
+        if transform == (1., 0.) {
+            return self.numeric();
+        }
+        self.numeric.resolve(self.tick_step(transform), 100.into())
+    }
+
+    /// `factor` and `offset` of label values, `price * factor + offset`
+    fn label_transform(&self) -> (f64, f64) {
+        match (self.scale, self.base) {
+            (PriceScale::Percentage(_), Some(base)) => (100. / *base, -100.),
+            (PriceScale::Rebased(_), Some(base)) => (100. / *base, 0.),
+            _ => (1., 0.),
+        }
+    }
+
+    /// Lowest and highest label value of a `label_transform`
+    fn label_range(&self, (factor, offset): (f64, f64)) -> (f64, f64) {
+        let (low, high) = (*self.min * factor + offset, *self.max * factor + offset);
+        (low.min(high), low.max(high))
+    }
+
+    /// Round distance between two labels in values of a `label_transform`. The step is made
+    /// finer when it would leave fewer than two labels. Zero when flat.
+    fn tick_step(&self, transform: (f64, f64)) -> Float {
+        if self.max <= self.min {
+            return Float::default();
+        }
+        let (low, high) = self.label_range(transform);
+        let count = max(self.height / self.tick_spacing, 1);
+        let mut step = nice_step((high - low) / count as f64);
+        while multiples(low, high, step).count() < 2 {
+            // 5 -> 2 -> 1 -> 0.5 times a power of ten
+            step = nice_step(step / 2.2);
+        }
+        OrderedFloat::from(step)
+    }
+
+    /// Row from the top containing `value`
+    pub(crate) fn row(&self, value: Float) -> u16 {
+        let row = self.height as f64 - 1. - self.calc_y(value).floor();
+        row.clamp(0., self.height.saturating_sub(1) as f64) as u16
+    }
+
+    /// Row from the top containing `value`, `None` outside the axis range
+    pub(crate) fn visible_row(&self, value: Float) -> Option<u16> {
+        (self.min..=self.max)
+            .contains(&value)
+            .then(|| self.row(value))
+    }
+
     pub fn calc_y(&self, value: Float) -> Float {
-        (value - self.min) / self.unit
+        (self.transform(value) - self.transform(self.min)) / self.unit
+    }
+
+    /// Rows from the top and values of the tick labels.
+    ///
+    /// A linear axis labels multiples of a round step picked for the tick spacing. A logarithmic
+    /// axis places powers of ten first, then 5 and 2 times powers of ten, keeping labels at
+    /// least the tick spacing apart, or labels round steps like a linear axis when fewer than
+    /// two of those fit. Flat ranges fall back to evenly spaced rows.
+    fn ticks(&self) -> Vec<(u16, Float)> {
+        let spacing = self.tick_spacing;
+        let even_ticks = (0..self.height).step_by(spacing as usize).map(|i| {
+            let value = self.transform(self.max) - self.unit * OrderedFloat::from(i);
+            (i, self.inverse(value))
+        });
+        if self.max <= self.min {
+            return even_ticks.collect();
+        }
+
+        if !self.is_logarithmic {
+            return self.step_ticks();
+        }
+
+        let mut ticks: Vec<(u16, Float)> = Vec::new();
+        let exponents = self.min.log10().floor() as i32..=self.max.log10().ceil() as i32;
+        for mantissa in [1., 5., 2.] {
+            for exponent in exponents.clone() {
+                let value = OrderedFloat::from(mantissa * 10f64.powi(exponent));
+                if value < self.min || value > self.max {
+                    continue;
+                }
+                let row = self.row(value);
+                if ticks
+                    .iter()
+                    .all(|(other, _)| row.abs_diff(*other) >= spacing)
+                {
+                    ticks.push((row, value));
+                }
+            }
+        }
+        ticks.sort();
+
+        if ticks.len() < 2 {
+            // narrow ranges hold no round powers, label round steps at their rows instead
+            let ticks = self.step_ticks();
+            if ticks.len() < 2 {
+                return even_ticks.collect();
+            }
+            return ticks;
+        }
+        ticks
+    }
+
+    /// Multiples of the round tick step, at most one per row
+    fn step_ticks(&self) -> Vec<(u16, Float)> {
+        let transform @ (factor, offset) = self.label_transform();
+        let step = *self.tick_step(transform);
+        let (low, high) = self.label_range(transform);
+
+        let mut ticks: Vec<(u16, Float)> = Vec::new();
+        for k in multiples(low, high, step).rev() {
+            let value = OrderedFloat::from((k as f64 * step - offset) / factor);
+            let row = self.row(value);
+            if ticks.last().is_none_or(|(last, _)| row > *last) {
+                ticks.push((row, value));
+            }
+        }
+        ticks.sort();
+        ticks
     }
 
     pub fn render(&self) -> Vec<String> {
         let mut result = Vec::new();
         let max_chars = max(
-            self.numeric.format(self.max).len(),
-            self.numeric.format(self.min).len(),
+            self.label(self.max).chars().count(),
+            self.label(self.min).chars().count(),
         );
+        let ticks = self.ticks();
         for i in 0..self.height {
-            let rendered = if i % 4 == 0 {
-                let value = self.max - self.unit * OrderedFloat::from(i);
-                format!(" {} ├ ", self.numeric.format(value))
+            let rendered = if let Some((_, value)) = ticks.iter().find(|(row, _)| *row == i) {
+                format!(" {:>max_chars$} ├ ", self.label(*value))
             } else {
                 format!(" {} │ ", " ".repeat(max_chars))
             };
@@ -81,6 +470,17 @@ impl YAxis {
 
         result
     }
+
+    /// Mirrored [`render`](Self::render) for an axis right of the plot, labels aligned left
+    pub fn render_right(&self) -> Vec<String> {
+        let ticks = self.ticks();
+        (0..self.height)
+            .map(|i| match ticks.iter().find(|(row, _)| *row == i) {
+                Some((_, value)) => format!(" ┤ {}", self.label(*value).trim_start()),
+                None => " │ ".to_string(),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -88,8 +488,9 @@ mod tests {
     use ordered_float::OrderedFloat;
 
     use crate::{
-        y_axis::{Numeric, YAxis},
         Float,
+        formatter::{Currency, Grouped, SiSuffix},
+        y_axis::{Numeric, PriceScale, YAxis},
     };
 
     #[test]
@@ -99,9 +500,189 @@ mod tests {
         assert_eq!(numeric.format(Float::from(99991)), "  99991.00");
     }
 
+    #[test]
+    fn auto_format() {
+        let numeric = Numeric::auto();
+        assert_eq!(numeric.format(Float::from(65000.25)), "65000");
+        assert_eq!(numeric.format(Float::from(0.0000123456)), "0.000012346");
+
+        let y_axis = YAxis::new(numeric, 8, 0.00001200.into(), 0.00001240.into());
+        assert_eq!(y_axis.numeric(), Numeric::new(0, 7));
+        assert_eq!(
+            y_axis.render(),
+            vec![
+                " 0.0000124 ├ ",
+                "           │ ",
+                "           │ ",
+                " 0.0000122 ├ ",
+                "           │ ",
+                "           │ ",
+                "           │ ",
+                " 0.0000120 ├ ",
+            ]
+        );
+
+        assert_eq!(Numeric::tick_size(0.00000001), Numeric::new(0, 8));
+        assert_eq!(Numeric::tick_size(5.), Numeric::new(0, 0));
+    }
+
+    #[test]
+    fn formatter() {
+        let numeric = Numeric::new(6, 1).formatter(SiSuffix::default());
+        assert_eq!(numeric.format(Float::from(65200)), " 65.2K");
+        assert_eq!(
+            YAxis::estimated_width(numeric.clone(), 0.into(), 2e6.into()),
+            10
+        );
+
+        let y_axis = YAxis::new(numeric, 5, 0.into(), 2e6.into());
+        assert_eq!(
+            y_axis.render(),
+            vec![
+                "   2.0M ├ ",
+                "        │ ",
+                "        │ ",
+                "        │ ",
+                "    0.0 ├ "
+            ]
+        );
+    }
+
+    #[test]
+    fn nice_ticks() {
+        let numeric = Numeric::new(6, 0).formatter(Grouped::default());
+        let y_axis = YAxis::new(numeric, 12, 42_130.into(), 42_270.into());
+        let labels = |y_axis: &YAxis| {
+            y_axis
+                .ticks()
+                .into_iter()
+                .map(|(_, value)| y_axis.label(value))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&y_axis), vec!["42,250", "42,200", "42,150"]);
+
+        let y_axis = y_axis.tick_spacing(2);
+        assert_eq!(
+            labels(&y_axis),
+            vec![
+                "42,260", "42,240", "42,220", "42,200", "42,180", "42,160", "42,140"
+            ]
+        );
+    }
+
     #[test]
     fn test_calc() {
         let y_axis = YAxis::new(Numeric::default(), 40, 100.into(), 200.into());
         assert_eq!(y_axis.calc_y(130.into()), OrderedFloat::from(12));
     }
+
+    #[test]
+    fn multibyte_labels() {
+        let numeric = Numeric::new(0, 0).formatter(Currency::new("€"));
+        assert_eq!(
+            YAxis::estimated_width(numeric.clone(), 80.into(), 120.into()),
+            8
+        );
+        let y_axis = YAxis::new(numeric, 5, 80.into(), 120.into());
+        assert_eq!(
+            y_axis.render(),
+            vec![" €120 ├ ", "      │ ", " €100 ├ ", "      │ ", "  €80 ├ "]
+        );
+    }
+
+    #[test]
+    fn render_right() {
+        let y_axis = YAxis::new(Numeric::new(6, 1), 5, 80.into(), 120.into());
+        assert_eq!(
+            y_axis.render_right(),
+            vec![" ┤ 120.0", " │ ", " ┤ 100.0", " │ ", " ┤ 80.0"]
+        );
+    }
+
+    #[test]
+    fn logarithmic() {
+        let y_axis = YAxis::new(Numeric::new(5, 0), 12, 10.into(), 1000.into())
+            .scale(PriceScale::Logarithmic);
+        assert!((*y_axis.calc_y(100.into()) - 6.).abs() < 1e-9);
+        assert_eq!(
+            y_axis.render(),
+            vec![
+                "  1000 ├ ",
+                "       │ ",
+                "       │ ",
+                "       │ ",
+                "       │ ",
+                "   100 ├ ",
+                "       │ ",
+                "       │ ",
+                "       │ ",
+                "       │ ",
+                "       │ ",
+                "    10 ├ ",
+            ]
+        );
+
+        // not positive, stays linear
+        let y_axis =
+            YAxis::new(Numeric::default(), 40, 0.into(), 200.into()).scale(PriceScale::Logarithmic);
+        assert_eq!(y_axis.calc_y(100.into()), OrderedFloat::from(20));
+
+        // no round powers within an intraday range, label round steps instead
+        let y_axis = YAxis::new(Numeric::default(), 12, 42_130.into(), 42_270.into())
+            .scale(PriceScale::Logarithmic);
+        let ticks = y_axis
+            .ticks()
+            .into_iter()
+            .map(|(row, value)| (row, y_axis.label(value)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ticks,
+            vec![
+                (1, "42250.000".to_string()),
+                (5, "42200.000".to_string()),
+                (10, "42150.000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn relative() {
+        let y_axis = YAxis::new(Numeric::new(6, 1), 5, 80.into(), 120.into())
+            .scale(PriceScale::Percentage(None))
+            .base(100.into());
+        assert_eq!(
+            y_axis.render(),
+            vec![
+                "   20.0% ├ ",
+                "         │ ",
+                "    0.0% ├ ",
+                "         │ ",
+                "  -20.0% ├ "
+            ]
+        );
+
+        let y_axis = YAxis::new(Numeric::new(6, 1), 5, 80.into(), 120.into())
+            .scale(PriceScale::Rebased(None))
+            .base(200.into());
+        assert_eq!(
+            y_axis.render(),
+            vec![
+                "   60.0 ├ ",
+                "        │ ",
+                "        │ ",
+                "        │ ",
+                "   40.0 ├ "
+            ]
+        );
+
+        assert_eq!(
+            YAxis::estimated_relative_width(
+                Numeric::new(6, 1),
+                PriceScale::Percentage(None),
+                (80.into(), 120.into()),
+                (80.into(), 120.into()),
+            ),
+            11
+        );
+    }
 }